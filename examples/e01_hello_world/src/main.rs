@@ -1,19 +1,17 @@
 use serenity::{
     async_trait,
-    client::{Context, EventHandler},
+    client::Context,
     model::{
         id::GuildId,
         interactions::{
-            application_command::ApplicationCommandInteraction, Interaction,
-            InteractionResponseType,
+            application_command::ApplicationCommandInteraction, InteractionResponseType,
         },
-        prelude::Ready,
     },
     prelude::GatewayIntents,
     Client,
 };
 use slashies::{
-    parsable::UserInput, register_commands, ApplicationCommandInteractionHandler, Commands,
+    framework::Framework, parsable::UserInput, ApplicationCommandInteractionHandler,
     InvocationError,
 };
 use slashies_macros::{Command, Commands};
@@ -34,12 +32,7 @@ impl ApplicationCommandInteractionHandler for HelloCommand {
         ctx: &Context,
         command: &ApplicationCommandInteraction,
     ) -> Result<(), InvocationError> {
-        let nickname = self
-            .user
-            .member
-            .as_ref()
-            .map(|pm| pm.nick.as_ref())
-            .flatten();
+        let nickname = self.user.member.as_ref().and_then(|pm| pm.nick.as_ref());
         let greeting = if let Some(nick) = nickname {
             format!("Hello {} aka {}", self.user.user.name, nick)
         } else {
@@ -51,8 +44,8 @@ impl ApplicationCommandInteractionHandler for HelloCommand {
                     .kind(InteractionResponseType::ChannelMessageWithSource)
                     .interaction_response_data(|message| message.content(greeting))
             })
-            .await
-            .map_err(|_| InvocationError)
+            .await?;
+        Ok(())
     }
 }
 
@@ -61,48 +54,6 @@ enum BotCommands {
     Hello(HelloCommand),
 }
 
-struct Handler;
-
-#[async_trait]
-impl EventHandler for Handler {
-    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        match interaction {
-            Interaction::ApplicationCommand(command_interaction) => {
-                BotCommands::parse(&ctx, &command_interaction)
-                    .expect("Failed to parse command")
-                    .invoke(&ctx, &command_interaction)
-                    .await
-                    .expect("Failed to invoke command");
-            }
-            _ => (),
-        }
-    }
-
-    async fn ready(&self, ctx: Context, ready: Ready) {
-        println!("{} is connected!", ready.user.name);
-        let guild_id = std::env::var("TEST_GUILD_ID").map(|id| {
-            id.parse()
-                .map(|id| GuildId(id))
-                .expect(&format!("Invalid test guild id {}", id))
-        });
-        let guild_id = match guild_id {
-            Ok(id) => Some(id),
-            Err(VarError::NotPresent) => None,
-            _ => panic!("Invalid guild id provided at $TEST_GUILD_ID"),
-        };
-        let commands = register_commands!(&ctx, guild_id, [HelloCommand])
-            .expect("Unable to register commands");
-        println!(
-            "Registered {} commands {}",
-            commands.len(),
-            match guild_id {
-                Some(id) => format!("for guild_id: {}", id),
-                None => "globally".to_owned(),
-            },
-        );
-    }
-}
-
 #[tokio::main]
 async fn main() {
     let token = std::env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
@@ -110,8 +61,21 @@ async fn main() {
         .expect("Expected a user id in the environment")
         .parse::<u64>()
         .expect("Invalid user id");
+    let guild_id = std::env::var("TEST_GUILD_ID").map(|id| {
+        id.parse()
+            .map(GuildId)
+            .unwrap_or_else(|_| panic!("Invalid test guild id {}", id))
+    });
+
+    // The framework registers the commands once the bot is ready, and routes interactions to them
+    let framework = match guild_id {
+        Ok(id) => Framework::<BotCommands>::new().register_in_guild(id),
+        Err(VarError::NotPresent) => Framework::<BotCommands>::new().register_globally(),
+        _ => panic!("Invalid guild id provided at $TEST_GUILD_ID"),
+    };
+
     let mut client = Client::builder(&token, GatewayIntents::empty())
-        .event_handler(Handler)
+        .event_handler(framework)
         .application_id(application_id)
         .await
         .expect("Err creating client");
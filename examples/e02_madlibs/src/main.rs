@@ -64,8 +64,7 @@ impl ApplicationCommandInteractionHandler for MadlibCommand {
             .user
             .member
             .as_ref()
-            .map(|pm| pm.nick.as_ref())
-            .flatten()
+            .and_then(|pm| pm.nick.as_ref())
             .unwrap_or(&self.user.user.name);
         let verb = self.verb.clone().unwrap_or("love".to_owned());
         let channel_name = self
@@ -124,8 +123,8 @@ impl ApplicationCommandInteractionHandler for MadlibCommand {
                         .kind(InteractionResponseType::ChannelMessageWithSource)
                         .interaction_response_data(|message| message.content(sentences.join(" ")))
                 })
-                .await
-                .map_err(|_| InvocationError)
+                .await?;
+            Ok(())
         } else {
             // Just ignore non-guild messages
             Ok(())
@@ -143,25 +142,19 @@ struct Handler;
 #[async_trait]
 impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        match interaction {
-            Interaction::ApplicationCommand(command_interaction) => {
-                BotCommands::parse(&ctx, &command_interaction)
-                    .expect("Failed to parse command")
-                    .invoke(&ctx, &command_interaction)
-                    .await
-                    .expect("Failed to invoke command");
-            }
-            _ => (),
+        if let Interaction::ApplicationCommand(command_interaction) = interaction {
+            BotCommands::parse(&ctx, &command_interaction)
+                .expect("Failed to parse command")
+                .invoke(&ctx, &command_interaction)
+                .await
+                .expect("Failed to invoke command");
         }
     }
 
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected!", ready.user.name);
-        let guild_id = std::env::var("TEST_GUILD_ID").map(|id| {
-            id.parse()
-                .map(|id| GuildId(id))
-                .expect("Invalid test guild id id")
-        });
+        let guild_id = std::env::var("TEST_GUILD_ID")
+            .map(|id| id.parse().map(GuildId).expect("Invalid test guild id id"));
         let guild_id = match guild_id {
             Ok(id) => Some(id),
             Err(VarError::NotPresent) => None,
@@ -26,6 +26,7 @@ mod movie;
 /// Recommend me a movie!
 #[derive(Debug, Command)]
 #[name = "recommend"]
+#[cooldown(per = "user", rate = 3, window_secs = 60)]
 struct RecommendCommand {
     /// The genre of movie to recommend
     #[choice("Action")]
@@ -90,7 +91,7 @@ impl ApplicationCommandInteractionHandler for RecommendCommand {
                                 embed
                                     .title(&rec.title)
                                     .field("Release Year", rec.release_year, false)
-                                    .field("Director(s)", &rec.directors.join(", "), false)
+                                    .field("Director(s)", rec.directors.join(", "), false)
                                     .field(
                                         "Average Rating",
                                         format!("{:.2} / 10.0", rec.average_rating),
@@ -158,25 +159,36 @@ struct Handler;
 #[async_trait]
 impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        match interaction {
-            Interaction::ApplicationCommand(command_interaction) => {
-                BotCommands::parse(&ctx, &command_interaction)
-                    .expect("Failed to parse command")
-                    .invoke(&ctx, &command_interaction)
+        if let Interaction::ApplicationCommand(command_interaction) = interaction {
+            let result = BotCommands::parse(&ctx, &command_interaction)
+                .expect("Failed to parse command")
+                .invoke(&ctx, &command_interaction)
+                .await;
+            if let Err(error) = result {
+                // Tell the user how long to wait if they're recommending movies too quickly
+                let cooldown = error
+                    .cooldown()
+                    .unwrap_or_else(|| panic!("Failed to invoke command: {}", error));
+                println!("Command on cooldown: {}", cooldown);
+                command_interaction
+                    .create_interaction_response(&ctx.http, |response| {
+                        response
+                            .kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|data| {
+                                data.content(error.user_message().unwrap_or_default())
+                                    .ephemeral(true)
+                            })
+                    })
                     .await
-                    .expect("Failed to invoke command");
+                    .expect("Failed to send response");
             }
-            _ => (),
         }
     }
 
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected!", ready.user.name);
-        let guild_id = std::env::var("TEST_GUILD_ID").map(|id| {
-            id.parse()
-                .map(|id| GuildId(id))
-                .expect("Invalid test guild id id")
-        });
+        let guild_id = std::env::var("TEST_GUILD_ID")
+            .map(|id| id.parse().map(GuildId).expect("Invalid test guild id id"));
         let guild_id = match guild_id {
             Ok(id) => Some(id),
             Err(VarError::NotPresent) => None,
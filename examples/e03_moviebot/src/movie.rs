@@ -24,7 +24,7 @@ pub struct MovieDatabase {
     pub imdb_sqlite_file: String,
 }
 
-const FETCH_RECOMMENDATIONS_WITH_MIN_RATING_STATEMENT: &'static str = "
+const FETCH_RECOMMENDATIONS_WITH_MIN_RATING_STATEMENT: &str = "
 SELECT titles.title_id, titles.primary_title, titles.premiered, titles.genres, ratings.rating, people.name
 FROM titles
 INNER JOIN ratings ON titles.title_id = ratings.title_id
@@ -44,7 +44,7 @@ AND titles.title_id IN (
     LIMIT ?3
 )";
 
-const FETCH_RECOMMENDATIONS_STATEMENT: &'static str = "
+const FETCH_RECOMMENDATIONS_STATEMENT: &str = "
 SELECT titles.title_id, titles.primary_title, titles.premiered, titles.genres, ratings.rating, people.name
 FROM titles
 INNER JOIN ratings ON titles.title_id = ratings.title_id
@@ -137,8 +137,7 @@ impl MovieDatabase {
                 movie.directors.push(row.director);
                 map
             })
-            .into_iter()
-            .map(|(_id, movie)| movie)
+            .into_values()
             .collect()
     }
 }
@@ -9,14 +9,16 @@ use serenity::{
             application_command::ApplicationCommandInteraction, Interaction,
             InteractionResponseType,
         },
+        permissions::Permissions,
         prelude::Ready,
     },
     prelude::{GatewayIntents, Mentionable},
     Client,
 };
 use slashies::{
-    parsable::UserInput, register_commands, ApplicationCommandInteractionHandler, Commands,
-    InvocationError,
+    checks::{Check, CheckFailure},
+    parsable::UserInput,
+    register_commands, ApplicationCommandInteractionHandler, Commands, InvocationError,
 };
 use slashies_macros::{
     ApplicationCommandInteractionHandler, Command, Commands, SubCommand, SubCommandGroup,
@@ -26,6 +28,8 @@ use std::env::VarError;
 /// Get or edit a user or group's permissions
 #[derive(Debug, Command, ApplicationCommandInteractionHandler)]
 #[name = "permissions"]
+#[default_member_permissions(MANAGE_ROLES)]
+#[dm_permission = false]
 enum PermissionsCommand {
     /// Get or edit a user's permissions
     #[name = "user"]
@@ -38,10 +42,36 @@ enum PermissionsCommand {
     Role(RoleSubCommandGroup),
 }
 
+/// Server admins can let anyone use the command, so make sure only members who can manage roles
+/// are able to edit permissions
+struct CanManageRoles;
+
+#[async_trait]
+impl Check for CanManageRoles {
+    async fn check(
+        _ctx: &Context,
+        command: &ApplicationCommandInteraction,
+    ) -> Result<(), CheckFailure> {
+        let permissions = command
+            .member
+            .as_ref()
+            .and_then(|member| member.permissions)
+            .unwrap_or_default();
+        if permissions.contains(Permissions::MANAGE_ROLES) {
+            Ok(())
+        } else {
+            Err(CheckFailure::new(
+                "You need the Manage Roles permission to edit permissions",
+            ))
+        }
+    }
+}
+
 #[derive(Debug, SubCommandGroup, ApplicationCommandInteractionHandler)]
 enum UserSubCommandGroup {
     /// Edit permissions for a user
     #[name = "edit"]
+    #[check(CanManageRoles)]
     Edit(EditPermissionsForUserCommand),
     /// Get permissions for a user
     #[name = "get"]
@@ -52,6 +82,7 @@ enum UserSubCommandGroup {
 enum RoleSubCommandGroup {
     /// Edit permissions for a role
     #[name = "edit"]
+    #[check(CanManageRoles)]
     Edit(EditPermissionsForRoleCommand),
     /// Get permissions for a role
     #[name = "get"]
@@ -118,7 +149,7 @@ impl ApplicationCommandInteractionHandler for GetPermissionsForUserCommand {
     ) -> Result<(), InvocationError> {
         let guild = command
             .guild_id
-            .expect("Command should only be called from a guild")
+            .expect("Command can't be used in DMs")
             .to_partial_guild(&ctx.http)
             .await
             .expect("Error getting guild");
@@ -230,7 +261,7 @@ impl ApplicationCommandInteractionHandler for GetPermissionsForRoleCommand {
     ) -> Result<(), InvocationError> {
         let guild = command
             .guild_id
-            .expect("Command should only be called from a guild")
+            .expect("Command can't be used in DMs")
             .to_partial_guild(&ctx.http)
             .await
             .expect("Error getting guild");
@@ -283,25 +314,34 @@ struct Handler;
 #[async_trait]
 impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        match interaction {
-            Interaction::ApplicationCommand(command_interaction) => {
-                BotCommands::parse(&ctx, &command_interaction)
-                    .expect("Failed to parse command")
-                    .invoke(&ctx, &command_interaction)
+        if let Interaction::ApplicationCommand(command_interaction) = interaction {
+            let result = BotCommands::parse(&ctx, &command_interaction)
+                .expect("Failed to parse command")
+                .invoke(&ctx, &command_interaction)
+                .await;
+            if let Err(error) = result {
+                // Let the user know why they can't use the command, but only them
+                let failure = error
+                    .check_failure()
+                    .unwrap_or_else(|| panic!("Failed to invoke command: {}", error));
+                command_interaction
+                    .create_interaction_response(&ctx.http, |response| {
+                        response
+                            .kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|data| {
+                                data.content(failure.reason()).ephemeral(true)
+                            })
+                    })
                     .await
-                    .expect("Failed to invoke command");
+                    .expect("Failed to send response");
             }
-            _ => (),
         }
     }
 
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} is connected!", ready.user.name);
-        let guild_id = std::env::var("TEST_GUILD_ID").map(|id| {
-            id.parse()
-                .map(|id| GuildId(id))
-                .expect("Invalid test guild id id")
-        });
+        let guild_id = std::env::var("TEST_GUILD_ID")
+            .map(|id| id.parse().map(GuildId).expect("Invalid test guild id id"));
         let guild_id = match guild_id {
             Ok(id) => Some(id),
             Err(VarError::NotPresent) => None,
@@ -0,0 +1,142 @@
+use serenity::{
+    async_trait,
+    client::{Context, EventHandler},
+    model::{
+        channel::Message,
+        id::GuildId,
+        interactions::{
+            application_command::ApplicationCommandInteraction, Interaction,
+            InteractionResponseType,
+        },
+        prelude::Ready,
+    },
+    prelude::{GatewayIntents, Mentionable},
+    Client,
+};
+use slashies::{
+    parsable::UserInput, register_commands, ApplicationCommandInteractionHandler, Commands,
+    InvocationError,
+};
+use slashies_macros::{Commands, MessageCommand, UserCommand};
+use std::env::VarError;
+
+#[derive(Debug, UserCommand)]
+#[name = "High Five"]
+struct HighFiveCommand {
+    user: UserInput,
+}
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for HighFiveCommand {
+    async fn invoke(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        let response = format!(
+            "{} high fived {}!",
+            command.user.mention(),
+            self.user.user.mention()
+        );
+        command
+            .create_interaction_response(&ctx.http, |response_builder| {
+                response_builder
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|message| message.content(response))
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, MessageCommand)]
+#[name = "Bookmark"]
+struct BookmarkCommand {
+    message: Message,
+}
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for BookmarkCommand {
+    async fn invoke(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        let link = self.message.link();
+        command
+            .create_interaction_response(&ctx.http, |response_builder| {
+                response_builder
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|message| {
+                        message
+                            .content(format!("Bookmarked {}", link))
+                            .ephemeral(true)
+                    })
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+// Messages are much larger than the other command targets, but each command is short-lived so
+// there is no need to box it
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Commands)]
+enum BotCommands {
+    HighFive(HighFiveCommand),
+    Bookmark(BookmarkCommand),
+}
+
+struct Handler;
+
+#[async_trait]
+impl EventHandler for Handler {
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Interaction::ApplicationCommand(command_interaction) = interaction {
+            BotCommands::parse(&ctx, &command_interaction)
+                .expect("Failed to parse command")
+                .invoke(&ctx, &command_interaction)
+                .await
+                .expect("Failed to invoke command");
+        }
+    }
+
+    async fn ready(&self, ctx: Context, ready: Ready) {
+        println!("{} is connected!", ready.user.name);
+        let guild_id = std::env::var("TEST_GUILD_ID")
+            .map(|id| id.parse().map(GuildId).expect("Invalid test guild id id"));
+        let guild_id = match guild_id {
+            Ok(id) => Some(id),
+            Err(VarError::NotPresent) => None,
+            _ => panic!("Invalid guild id provided at $TEST_GUILD_ID"),
+        };
+        let commands = register_commands!(&ctx, guild_id, [HighFiveCommand, BookmarkCommand])
+            .expect("Unable to register commands");
+        println!(
+            "Registered {} commands {}",
+            commands.len(),
+            match guild_id {
+                Some(id) => format!("for guild_id: {}", id),
+                None => "globally".to_owned(),
+            },
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let token = std::env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
+    let application_id = std::env::var("DISCORD_USER_ID")
+        .expect("Expected a user id in the environment")
+        .parse::<u64>()
+        .expect("Invalid user id");
+    let mut client = Client::builder(&token, GatewayIntents::empty())
+        .event_handler(Handler)
+        .application_id(application_id)
+        .await
+        .expect("Err creating client");
+
+    if let Err(why) = client.start().await {
+        println!("Client error: {:?}", why);
+    }
+}
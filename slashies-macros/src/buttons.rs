@@ -0,0 +1,227 @@
+use proc_macro::TokenStream;
+use proc_macro_error::abort;
+use quote::{quote, ToTokens};
+use syn::{Attribute, DataEnum, Ident, Meta, NestedMeta, Variant};
+
+use crate::utility::{self, MAX_CUSTOM_ID_LENGTH};
+
+/// Discord's limit on the length of button labels
+const MAX_LABEL_LENGTH: usize = 80;
+/// Discord's limit on the number of buttons in a message (5 action rows of 5 buttons)
+const MAX_BUTTONS: usize = 25;
+
+/// For each button, we need four sections of code:
+/// - parse_arm: Construct the variant from the rest of the custom ID, given the variant's name
+/// - custom_id_arm: Build the custom ID from the variant's name and the values of its fields
+/// - create_arm: Fill in the label, style and emoji of the button
+/// - length_check: Check that the custom ID always fits if all of the fields have a maximum length
+struct ButtonTokenSections {
+    parse_arm: proc_macro2::TokenStream,
+    custom_id_arm: proc_macro2::TokenStream,
+    create_arm: proc_macro2::TokenStream,
+    length_check: proc_macro2::TokenStream,
+}
+
+fn get_style(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let style = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("style"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) if list.nested.len() == 1 => match list.nested[0] {
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("primary") => {
+                    quote! { Primary }
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("secondary") => {
+                    quote! { Secondary }
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("success") => {
+                    quote! { Success }
+                }
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("danger") => {
+                    quote! { Danger }
+                }
+                ref style => abort!(
+                    style,
+                    "Unknown button style. Supported styles are \"primary\", \"secondary\", \"success\" and \"danger\""
+                ),
+            },
+            _ => abort!(
+                attr,
+                "Invalid \"style\" attribute. Attribute must be of the form #[style(primary)]"
+            ),
+        })
+        .unwrap_or_else(|| quote! { Primary });
+    quote! { serenity::model::interactions::message_component::ButtonStyle::#style }
+}
+
+fn button_token_sections_from_variant(
+    identifier: &Ident,
+    custom_id: &str,
+    variant: &Variant,
+) -> ButtonTokenSections {
+    let variant_ident = &variant.ident;
+    let variant_name = variant_ident.to_string();
+    let label = utility::get_description(variant.attrs.as_slice())
+        .unwrap_or_else(|| {
+            abort!(
+                variant_ident,
+                "Buttons must specify a label via a docstring"
+            )
+        })
+        .trim()
+        .to_owned();
+    utility::validate_label(
+        "Button labels",
+        &label,
+        MAX_LABEL_LENGTH,
+        variant.attrs.as_slice(),
+    );
+    let style = get_style(variant.attrs.as_slice());
+    let emoji = utility::get_emoji(variant.attrs.as_slice());
+
+    let bindings = utility::field_bindings(&variant.fields);
+    let field_types = variant
+        .fields
+        .iter()
+        .map(|field| field.ty.to_token_stream())
+        .collect::<Vec<_>>();
+    let parse_fields = field_types.iter().map(|field_type| {
+        quote! {
+            parts
+                .next()
+                .and_then(<#field_type as slashies::parsable::CustomIdField>::parse_custom_id)
+                .ok_or_else(invalid)?
+        }
+    });
+    let (constructor, pattern) = match variant.fields {
+        syn::Fields::Named(_) => (
+            quote! { Self::#variant_ident { #(#bindings: #parse_fields,)* } },
+            quote! { Self::#variant_ident { #(#bindings,)* } },
+        ),
+        syn::Fields::Unnamed(_) => (
+            quote! { Self::#variant_ident(#(#parse_fields,)*) },
+            quote! { Self::#variant_ident(#(#bindings,)*) },
+        ),
+        syn::Fields::Unit => (
+            quote! { Self::#variant_ident },
+            quote! { Self::#variant_ident },
+        ),
+    };
+
+    // The custom ID of the enum and the variant's name, followed by a separator before each field
+    let header_length = custom_id.chars().count() + 1 + variant_name.chars().count();
+    let length_error = format!(
+        "The custom ID of {}::{} can be too long (at most {} characters)",
+        identifier, variant_name, MAX_CUSTOM_ID_LENGTH
+    );
+
+    ButtonTokenSections {
+        parse_arm: quote! {
+            Some(#variant_name) => #constructor,
+        },
+        custom_id_arm: quote! {
+            #pattern => vec![
+                #variant_name.to_owned(),
+                #(<#field_types as slashies::parsable::CustomIdField>::to_custom_id(#bindings),)*
+            ],
+        },
+        create_arm: quote! {
+            Self::#variant_ident { .. } => button
+                .label(#label)
+                .style(#style)
+                #emoji,
+        },
+        length_check: utility::custom_id_length_check(header_length, &field_types, &length_error),
+    }
+}
+
+pub fn impl_buttons_for_enum(
+    identifier: Ident,
+    attrs: &[Attribute],
+    data: &DataEnum,
+) -> TokenStream {
+    let custom_id = utility::get_custom_id("Button", &identifier, attrs);
+    if custom_id.contains(':') {
+        abort!(
+            identifier,
+            "Button custom IDs must not contain ':', as it separates the values of the button's fields"
+        );
+    }
+    if data.variants.is_empty() {
+        abort!(identifier, "Buttons must have at least one variant");
+    }
+    if let Some(extra) = data.variants.iter().nth(MAX_BUTTONS) {
+        abort!(
+            extra,
+            "Buttons can have at most {} variants, as a message can only have 5 rows of 5 buttons",
+            MAX_BUTTONS
+        );
+    }
+    let sections = data
+        .variants
+        .iter()
+        .map(|variant| button_token_sections_from_variant(&identifier, &custom_id, variant))
+        .collect::<Vec<_>>();
+    let parse_arm = sections.iter().map(|sections| &sections.parse_arm);
+    let custom_id_arm = sections.iter().map(|sections| &sections.custom_id_arm);
+    let create_arm = sections.iter().map(|sections| &sections.create_arm);
+    let length_check = sections.iter().map(|sections| &sections.length_check);
+
+    quote! {
+        #(#length_check)*
+
+        impl slashies::Buttons for #identifier {
+            fn base_custom_id() -> String {
+                #custom_id.to_owned()
+            }
+
+            fn parse_custom_id(custom_id: &str) -> Result<Self, slashies::ParseError> {
+                let unknown = || slashies::ParseError::UnknownComponent {
+                    custom_id: custom_id.to_owned(),
+                };
+                let invalid = || slashies::ParseError::InvalidCustomId {
+                    custom_id: custom_id.to_owned(),
+                };
+                let mut parts = custom_id.split(':');
+                if parts.next() != Some(#custom_id) {
+                    return Err(unknown());
+                }
+                let button = match parts.next() {
+                    #(#parse_arm)*
+                    _ => return Err(unknown()),
+                };
+                match parts.next() {
+                    Some(_) => Err(invalid()),
+                    None => Ok(button),
+                }
+            }
+
+            fn custom_id(&self) -> String {
+                let parts = match self {
+                    #(#custom_id_arm)*
+                };
+                format!("{}:{}", #custom_id, parts.join(":"))
+            }
+
+            fn create<'a>(
+                &self,
+                button: &'a mut serenity::builder::CreateButton,
+            ) -> &'a mut serenity::builder::CreateButton {
+                button.custom_id(self.custom_id());
+                match self {
+                    #(#create_arm)*
+                }
+            }
+        }
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn examples_fail_with_correct_error() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/buttons/*.rs");
+    }
+}
@@ -3,19 +3,25 @@ use itertools::Itertools;
 use proc_macro::TokenStream;
 use proc_macro_error::abort;
 use quote::{quote, ToTokens};
-use syn::{DataStruct, Field, Ident, Lit};
+use syn::{ext::IdentExt, Attribute, DataStruct, Field, Ident, Lit};
 
-/// For each command option, we need four sections of code:
+/// For each command option, we need five sections of code (plus its character count):
 /// - parse_fetch: Parse the field from a discord command interaction option into a variable
 /// - parse_struct_item: Add the field to the resulting struct
 /// - is_required: Whether the command option is required (used to order command options as required options must be added first when registering the command)
 /// - registration_fn: Register the command option
+/// - autocomplete_arm: Route an autocomplete interaction to the option's handler (if it has one)
+///
+/// The character count is how many characters the option's name, description and choices count
+/// towards Discord's 4000 character limit for a command.
 #[derive(Debug)]
 pub struct OptionTokenSections {
     pub parse_fetch: proc_macro2::TokenStream,
     pub parse_struct_item: proc_macro2::TokenStream,
     pub is_required: proc_macro2::TokenStream,
     pub registration_fn: proc_macro2::TokenStream,
+    pub autocomplete_arm: Option<proc_macro2::TokenStream>,
+    pub character_count: usize,
 }
 
 fn option_token_sections_from_field(field: &Field) -> OptionTokenSections {
@@ -25,23 +31,64 @@ fn option_token_sections_from_field(field: &Field) -> OptionTokenSections {
             "Unnamed struct fields are not supported for commands",
         );
     });
-    let option_name = field_ident.to_string();
+    let option_name = field_ident.unraw().to_string();
+    utility::validate_chat_input_name("Option", &option_name, field_ident);
 
-    let description = utility::get_description(field.attrs.as_slice()).unwrap_or_else(|| {
-        abort!(
-            field_ident,
-            "Command options must specify a description via a docstring"
-        )
-    });
-    let choices = utility::get_choices(field.attrs.as_slice());
+    let description = utility::get_description(field.attrs.as_slice())
+        .unwrap_or_else(|| {
+            abort!(
+                field_ident,
+                "Command options must specify a description via a docstring"
+            )
+        })
+        .trim()
+        .to_owned();
+    utility::validate_description("Option", &description, field.attrs.as_slice());
+    let localizations = utility::get_localization_calls("Option", field.attrs.as_slice());
+    let (choices, choice_character_counts): (Vec<_>, Vec<_>) =
+        utility::get_choices(field.attrs.as_slice())
+            .into_iter()
+            .unzip();
+    let character_count = option_name.chars().count()
+        + description.chars().count()
+        + choice_character_counts.into_iter().sum::<usize>();
     let channel_types = utility::get_channel_types(field.attrs.as_slice());
     let min_value = utility::get_minimum_value(field.attrs.as_slice());
     let max_value = utility::get_maximum_value(field.attrs.as_slice());
+    let autocomplete_handler = utility::get_autocomplete_handler(field.attrs.as_slice());
+    if autocomplete_handler.is_some() && !choices.is_empty() {
+        let autocomplete_attr = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("autocomplete"));
+        abort!(
+            autocomplete_attr,
+            "Options can't use both autocomplete and choices, as Discord only allows one of them"
+        );
+    }
+    let set_autocomplete = autocomplete_handler
+        .as_ref()
+        .map(|_| quote! { .set_autocomplete(true) });
 
     let field_type = field.ty.to_token_stream();
+    // Whether the type fixes the range of the option is only known once the compiler knows the
+    // type, so it is checked in a constant rather than in the macro
+    let bounds_check = (min_value.is_some() || max_value.is_some()).then(|| {
+        let bounds_error = format!(
+            "Option `{}` can't use \"min\" or \"max\" attributes, as its type already sets the range",
+            option_name
+        );
+        quote! {
+            const _: () = assert!(
+                !<#field_type as slashies::parsable::ParsableCommandOption>::HAS_BOUNDS,
+                #bounds_error
+            );
+        }
+    });
     OptionTokenSections {
         parse_fetch: quote! {
-            let #field_ident = <#field_type as slashies::parsable::ParsableCommandOption>::parse_from(options.get(#option_name))?;
+            let #field_ident = <#field_type as slashies::parsable::ParsableCommandOption>::parse_from(options.get(#option_name))
+                .map_err(|error| error.in_option(#option_name))?;
         },
         parse_struct_item: quote! {
             #field_ident,
@@ -50,26 +97,42 @@ fn option_token_sections_from_field(field: &Field) -> OptionTokenSections {
             <#field_type as slashies::parsable::ParsableCommandOption>::is_required()
         },
         registration_fn: quote! {
-            |option: &mut serenity::builder::CreateApplicationCommandOption| option
-                .kind(<#field_type as slashies::parsable::ParsableCommandOption>::application_command_option_type())
-                .name(#option_name)
-                .description(#description)
-                .required(<#field_type as slashies::parsable::ParsableCommandOption>::is_required())
-                #(#choices)*
-                #channel_types
-                #min_value
-                #max_value
+            |option: &mut serenity::builder::CreateApplicationCommandOption| {
+                #bounds_check
+                <#field_type as slashies::parsable::ParsableCommandOption>::register_option(option)
+                    .kind(<#field_type as slashies::parsable::ParsableCommandOption>::application_command_option_type())
+                    .name(#option_name)
+                    .description(#description)
+                    #localizations
+                    .required(<#field_type as slashies::parsable::ParsableCommandOption>::is_required())
+                    #(#choices)*
+                    #channel_types
+                    #min_value
+                    #max_value
+                    #set_autocomplete
+            }
         },
+        autocomplete_arm: autocomplete_handler.map(|handler| {
+            quote! {
+                #option_name => #handler(ctx, interaction, focused).await,
+            }
+        }),
+        character_count,
     }
 }
 
 pub fn options_for_struct_data(data: &DataStruct) -> Vec<OptionTokenSections> {
     match data.fields {
-        syn::Fields::Named(_) => data
-            .fields
-            .iter()
-            .map(|field| option_token_sections_from_field(field))
-            .collect(),
+        syn::Fields::Named(_) => {
+            utility::validate_count(
+                data.fields.iter(),
+                "Commands and subcommands can have at most 25 options",
+            );
+            data.fields
+                .iter()
+                .map(option_token_sections_from_field)
+                .collect()
+        }
         syn::Fields::Unit => Vec::new(),
         _ => abort!(
             data.fields,
@@ -82,14 +145,19 @@ pub fn impl_command_for_struct(
     identifier: Ident,
     name: Lit,
     description: &str,
+    localizations: proc_macro2::TokenStream,
+    attrs: &[Attribute],
+    permission_methods: proc_macro2::TokenStream,
     options: Vec<OptionTokenSections>,
 ) -> TokenStream {
-    let (parse_fetch, parse_struct_item, is_required, registration_fn): (
-        Vec<_>,
-        Vec<_>,
-        Vec<_>,
-        Vec<_>,
-    ) = options
+    let (
+        parse_fetch,
+        parse_struct_item,
+        is_required,
+        registration_fn,
+        autocomplete_arm,
+        character_count,
+    ): (Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>) = options
         .into_iter()
         .map(|option| {
             let OptionTokenSections {
@@ -97,25 +165,49 @@ pub fn impl_command_for_struct(
                 parse_struct_item,
                 is_required,
                 registration_fn,
+                autocomplete_arm,
+                character_count,
             } = option;
-            (parse_fetch, parse_struct_item, is_required, registration_fn)
+            (
+                parse_fetch,
+                parse_struct_item,
+                is_required,
+                registration_fn,
+                autocomplete_arm,
+                character_count,
+            )
         })
         .multiunzip();
+    let autocomplete_impl = impl_autocomplete_for_struct(&identifier, autocomplete_arm);
+    let route_autocomplete = route_autocomplete_fn();
+    let own_character_count = utility::literal_length(&name) + description.chars().count();
+    let character_count_check = utility::character_count_check(&identifier);
+    let localization_imports = utility::localization_imports();
+
+    let checks = utility::get_check_calls(attrs);
+    let cooldowns = utility::get_cooldowns(attrs, quote! { #name.to_owned() });
 
     let output = quote! {
+        #[serenity::async_trait]
         impl slashies::Command for #identifier {
+            const CHARACTER_COUNT: usize = #own_character_count #(+ #character_count)*;
+
             fn parse(command: &serenity::model::interactions::application_command::ApplicationCommandInteraction) -> Result<Self, slashies::ParseError> {
-                let options: std::collections::HashMap<String, serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption> = command.data
-                    .options
-                    .iter()
-                    .map(|option| (option.name.clone(), option.clone()))
-                    .collect();
+                let parse = || -> Result<Self, slashies::ParseError> {
+                    let options: std::collections::HashMap<String, serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption> = command.data
+                        .options
+                        .iter()
+                        .map(|option| (option.name.clone(), option.clone()))
+                        .collect();
 
-                #(#parse_fetch)*
+                    #(#parse_fetch)*
 
-                Ok(Self {
-                    #(#parse_struct_item)*
-                })
+                    Ok(Self {
+                        #(#parse_struct_item)*
+                    })
+                };
+                // Add the command name to the start of the path of any errors
+                parse().map_err(|error| error.in_option(#name))
             }
 
             fn name() -> String {
@@ -123,6 +215,7 @@ pub fn impl_command_for_struct(
             }
 
             fn register(command: &mut serenity::builder::CreateApplicationCommand) -> &mut serenity::builder::CreateApplicationCommand {
+                #localization_imports
                 // Ensure required options are added first
                 let mut options: Vec<(bool, Box<dyn Fn(&mut serenity::builder::CreateApplicationCommandOption) -> &mut serenity::builder::CreateApplicationCommandOption>)> = vec![
                     #((#is_required, Box::new(#registration_fn)),)*
@@ -135,23 +228,91 @@ pub fn impl_command_for_struct(
 
                 let mut command = command
                     .name(#name)
-                    .description(#description);
+                    .description(#description)
+                    #localizations;
 
                 for (_, registration_fn) in options {
                     command = command.create_option(registration_fn);
                 }
-                command
+                <Self as slashies::Command>::register_permissions(command)
             }
+
+            #permission_methods
+
+            async fn run_checks(
+                &self,
+                ctx: &serenity::prelude::Context,
+                command: &serenity::model::interactions::application_command::ApplicationCommandInteraction,
+            ) -> Result<(), slashies::InvocationError> {
+                #checks
+                Ok(())
+            }
+
+            fn cooldowns(&self) -> Vec<slashies::cooldown::DeclaredCooldown> {
+                vec![#(#cooldowns,)*]
+            }
+
+            #route_autocomplete
         }
+
+        #character_count_check
+
+        #autocomplete_impl
     };
     output.into()
 }
 
+/// Forward the trait's `route_autocomplete` to the generated `AutocompleteHandler` implementation
+pub fn route_autocomplete_fn() -> proc_macro2::TokenStream {
+    quote! {
+        async fn route_autocomplete(
+            ctx: &serenity::prelude::Context,
+            interaction: &serenity::model::interactions::autocomplete::AutocompleteInteraction,
+            options: &[serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption],
+        ) -> Result<(), slashies::InvocationError> {
+            <Self as slashies::AutocompleteHandler>::autocomplete(ctx, interaction, options).await
+        }
+    }
+}
+
+/// Route autocomplete interactions to the handler for whichever option is focused
+pub fn impl_autocomplete_for_struct(
+    identifier: &Ident,
+    autocomplete_arm: Vec<Option<proc_macro2::TokenStream>>,
+) -> proc_macro2::TokenStream {
+    let autocomplete_arm = autocomplete_arm.into_iter().flatten().collect::<Vec<_>>();
+    if autocomplete_arm.is_empty() {
+        return quote! {
+            impl slashies::AutocompleteHandler for #identifier {}
+        };
+    }
+    quote! {
+        #[serenity::async_trait]
+        impl slashies::AutocompleteHandler for #identifier {
+            async fn autocomplete(
+                ctx: &serenity::prelude::Context,
+                interaction: &serenity::model::interactions::autocomplete::AutocompleteInteraction,
+                options: &[serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption],
+            ) -> Result<(), slashies::InvocationError> {
+                let focused = options
+                    .iter()
+                    .find(|option| option.focused)
+                    .ok_or_else(|| slashies::InvocationError::new("no option is focused"))?;
+                match focused.name.as_str() {
+                    #(#autocomplete_arm)*
+                    _ => Err(slashies::InvocationError::new("no autocomplete handler for the focused option")),
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
     fn examples_fail_with_correct_error() {
         let t = trybuild::TestCases::new();
-        t.compile_fail("tests/command/*.rs");
+        t.pass("tests/command/pass_*.rs");
+        t.compile_fail("tests/command/e*.rs");
     }
 }
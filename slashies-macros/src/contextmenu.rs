@@ -0,0 +1,169 @@
+use proc_macro::TokenStream;
+use proc_macro_error::abort;
+use quote::{quote, ToTokens};
+use syn::{Attribute, DataStruct, Ident, Lit};
+
+use crate::utility;
+
+/// Build the expression constructing the struct from the command's target, whether the target is
+/// held in a named field, an unnamed field or not at all (for unit structs)
+pub fn target_constructor(data: &DataStruct) -> proc_macro2::TokenStream {
+    if !matches!(data.fields, syn::Fields::Unit) && data.fields.len() != 1 {
+        abort!(
+            data.fields,
+            "User and message commands must either be unit structs or have exactly one field, containing the target of the command"
+        );
+    }
+    match data.fields {
+        syn::Fields::Named(ref fields) => {
+            let field = &fields.named[0];
+            let field_ident = &field.ident;
+            let field_type = field.ty.to_token_stream();
+            quote! {
+                Self {
+                    #field_ident: <#field_type as slashies::parsable::ParsableTarget>::parse_target(command)?,
+                }
+            }
+        }
+        syn::Fields::Unnamed(ref fields) => {
+            let field_type = fields.unnamed[0].ty.to_token_stream();
+            quote! {
+                Self(<#field_type as slashies::parsable::ParsableTarget>::parse_target(command)?)
+            }
+        }
+        syn::Fields::Unit => quote! {
+            {
+                let _ = command;
+                Self
+            }
+        },
+    }
+}
+
+pub fn impl_user_command_for_struct(
+    identifier: Ident,
+    name: Lit,
+    attrs: &[Attribute],
+    data: &DataStruct,
+) -> TokenStream {
+    impl_context_menu_command_for_struct(
+        identifier,
+        name,
+        attrs,
+        data,
+        quote! { slashies::UserCommand },
+        quote! { serenity::model::interactions::application_command::ApplicationCommandType::User },
+    )
+}
+
+pub fn impl_message_command_for_struct(
+    identifier: Ident,
+    name: Lit,
+    attrs: &[Attribute],
+    data: &DataStruct,
+) -> TokenStream {
+    impl_context_menu_command_for_struct(
+        identifier,
+        name,
+        attrs,
+        data,
+        quote! { slashies::MessageCommand },
+        quote! { serenity::model::interactions::application_command::ApplicationCommandType::Message },
+    )
+}
+
+/// Check that a user or message command name is between 1 and 32 characters long (unlike slash
+/// command names, they may contain capital letters and spaces)
+fn validate_name<T: ToTokens>(name: &str, span: T) {
+    let length = name.chars().count();
+    if !(1..=32).contains(&length) {
+        abort!(
+            span,
+            "Command names must be between 1 and 32 characters long, but this one is {} characters long",
+            length
+        );
+    }
+}
+
+/// User and message commands share the same shape - they implement their own trait, then
+/// implement [`Command`] by delegating to it so that they can be registered and dispatched along
+/// with regular slash commands
+fn impl_context_menu_command_for_struct(
+    identifier: Ident,
+    name: Lit,
+    attrs: &[Attribute],
+    data: &DataStruct,
+    command_trait: proc_macro2::TokenStream,
+    command_kind: proc_macro2::TokenStream,
+) -> TokenStream {
+    match name {
+        Lit::Str(ref name_str) => validate_name(&name_str.value(), &name),
+        _ => abort!(name, "Command names must be string literals"),
+    }
+    let constructor = target_constructor(data);
+    let permission_methods = utility::get_permission_methods(attrs);
+    // Only override the default registration if there are localized names to add to it
+    let localized_names = utility::get_localizations(attrs, "name_localized");
+    let register = if localized_names.is_empty() {
+        quote! {}
+    } else {
+        for (_, localized_name) in &localized_names {
+            validate_name(&localized_name.value(), localized_name);
+        }
+        let (locales, localized_names): (Vec<_>, Vec<_>) = localized_names.into_iter().unzip();
+        quote! {
+            fn register(command: &mut serenity::builder::CreateApplicationCommand) -> &mut serenity::builder::CreateApplicationCommand {
+                use slashies::localization::Localize as _;
+                command
+                    .name(Self::name())
+                    .kind(#command_kind)
+                    #(.name_localized(#locales, #localized_names))*
+            }
+        }
+    };
+    quote! {
+        impl #command_trait for #identifier {
+            fn parse(command: &serenity::model::interactions::application_command::ApplicationCommandInteraction) -> Result<Self, slashies::ParseError> {
+                Ok(#constructor)
+            }
+
+            fn name() -> String {
+                #name.to_owned()
+            }
+
+            #register
+        }
+
+        impl slashies::Command for #identifier {
+            fn parse(command: &serenity::model::interactions::application_command::ApplicationCommandInteraction) -> Result<Self, slashies::ParseError> {
+                <Self as #command_trait>::parse(command)
+            }
+
+            fn name() -> String {
+                <Self as #command_trait>::name()
+            }
+
+            fn kind() -> serenity::model::interactions::application_command::ApplicationCommandType {
+                #command_kind
+            }
+
+            fn register(command: &mut serenity::builder::CreateApplicationCommand) -> &mut serenity::builder::CreateApplicationCommand {
+                let command = <Self as #command_trait>::register(command);
+                <Self as slashies::Command>::register_permissions(command)
+            }
+
+            #permission_methods
+        }
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn examples_fail_with_correct_error() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/usercommand/*.rs");
+        t.compile_fail("tests/messagecommand/*.rs");
+    }
+}
@@ -0,0 +1,111 @@
+use proc_macro::TokenStream;
+use proc_macro_error::abort;
+use quote::{quote, ToTokens};
+use syn::{Attribute, DataStruct, Ident, Lit, Meta, MetaNameValue};
+
+use crate::utility::{self, MAX_CUSTOM_ID_LENGTH};
+
+fn get_version(attrs: &[Attribute]) -> u32 {
+    attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("version"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Int(ref version),
+                ..
+            })) => version
+                .base10_parse::<u32>()
+                .unwrap_or_else(|_| abort!(version, "Invalid \"version\" attribute")),
+            _ => abort!(
+                attr,
+                "Invalid \"version\" attribute. Attribute must be of the form #[version = 1]"
+            ),
+        })
+        .unwrap_or(1)
+}
+
+pub fn impl_custom_id_codec_for_struct(
+    identifier: Ident,
+    attrs: &[Attribute],
+    data: &DataStruct,
+) -> TokenStream {
+    let custom_id = utility::get_custom_id("State", &identifier, attrs);
+    if custom_id.contains(':') {
+        abort!(
+            identifier,
+            "State custom IDs must not contain ':', as it separates the values of the fields"
+        );
+    }
+    let version = get_version(attrs);
+
+    let bindings = utility::field_bindings(&data.fields);
+    let field_types = data
+        .fields
+        .iter()
+        .map(|field| field.ty.to_token_stream())
+        .collect::<Vec<_>>();
+    let parse_fields = field_types
+        .iter()
+        .zip(&bindings)
+        .map(|(field_type, binding)| {
+            quote! {
+                <#field_type as slashies::parsable::CustomIdField>::parse_custom_id(#binding)?
+            }
+        });
+    let (constructor, pattern) = match data.fields {
+        syn::Fields::Named(_) => (
+            quote! { Self { #(#bindings: #parse_fields,)* } },
+            quote! { Self { #(#bindings,)* } },
+        ),
+        syn::Fields::Unnamed(_) => (
+            quote! { Self(#(#parse_fields,)*) },
+            quote! { Self(#(#bindings,)*) },
+        ),
+        syn::Fields::Unit => (quote! { Self }, quote! { Self }),
+    };
+    // The custom ID and version, followed by a separator before each field
+    let header_length = custom_id.chars().count() + 1 + version.to_string().len();
+    let length_error = format!(
+        "The fields of {} can be too long to fit in a custom ID (at most {} characters)",
+        identifier, MAX_CUSTOM_ID_LENGTH
+    );
+    let length_check = utility::custom_id_length_check(header_length, &field_types, &length_error);
+
+    quote! {
+        #length_check
+
+        impl slashies::CustomIdCodec for #identifier {
+            fn custom_id() -> String {
+                #custom_id.to_owned()
+            }
+
+            fn version() -> u32 {
+                #version
+            }
+
+            fn encode_fields(&self) -> Vec<String> {
+                let #pattern = self;
+                vec![
+                    #(<#field_types as slashies::parsable::CustomIdField>::to_custom_id(#bindings),)*
+                ]
+            }
+
+            fn decode_fields(fields: &[&str]) -> Option<Self> {
+                match fields {
+                    [#(#bindings,)*] => Some(#constructor),
+                    _ => None,
+                }
+            }
+        }
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn examples_fail_with_correct_error() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/customidcodec/*.rs");
+    }
+}
@@ -1,53 +1,59 @@
+use buttons::impl_buttons_for_enum;
 use command::{impl_command_for_struct, options_for_struct_data};
 use commands::get_commands_variant_info;
+use contextmenu::{impl_message_command_for_struct, impl_user_command_for_struct};
+use customidcodec::impl_custom_id_codec_for_struct;
 use itertools::Itertools;
+use modal::impl_modal_for_struct;
 use proc_macro::{self, TokenStream};
 use proc_macro_error::{proc_macro_error, abort};
 use quote::quote;
+use selectmenu::impl_select_menu_for_enum;
 use subcommand::{impl_subcommand_for_struct, impl_command_for_enum, subcommands_for_enum};
 use subcommandgroup::impl_subcommandgroup_for_enum;
-use syn::{parse_macro_input, DeriveInput, Ident, Meta};
+use syn::{parse_macro_input, DeriveInput, Ident};
 
+mod buttons;
 mod command;
 mod commands;
+mod contextmenu;
+mod customidcodec;
+mod modal;
+mod selectmenu;
 mod subcommand;
 mod subcommandgroup;
 mod utility;
 
 #[proc_macro_error]
-#[proc_macro_derive(Command, attributes(name, subcommandgroup, choice, channel_types, min, max))]
+#[proc_macro_derive(Command, attributes(name, subcommandgroup, choice, channel_types, min, max, autocomplete, name_localized, description_localized, default_member_permissions, dm_permission, check, cooldown))]
 pub fn derive_commmand(input: TokenStream) -> TokenStream {
     let DeriveInput {
         ident, data, attrs, ..
     } = parse_macro_input!(input);
 
-    let name_attr = attrs
-        .iter()
-        .find(|attr| attr.path.is_ident("name"))
-        .unwrap_or_else(|| abort!(ident, "Command must specify a name via the \"name\" attribute"));
-    let name_meta = name_attr
-        .parse_meta()
-        .unwrap_or_else(|_| abort!(name_attr, "Invalid \"name\" attribute"));
-    let name = match name_meta {
-        Meta::NameValue(value) => value.lit,
-        _ => abort!(name_attr, "Invalid \"name\" attribute"),
-    };
+    let name = utility::get_command_name(&ident, attrs.as_slice());
+    utility::validate_chat_input_name_lit("Command", &name);
     let description = utility::get_description(attrs.as_slice())
-        .unwrap_or_else(|| abort!(ident, "Command must specify a description via a docstring"));
+        .unwrap_or_else(|| abort!(ident, "Command must specify a description via a docstring"))
+        .trim()
+        .to_owned();
+    utility::validate_description("Command", &description, attrs.as_slice());
+    let localizations = utility::get_localization_calls("Command", attrs.as_slice());
+    let permission_methods = utility::get_permission_methods(attrs.as_slice());
 
     match data {
         syn::Data::Struct(ref data) => {
-            impl_command_for_struct(ident, name, &description, options_for_struct_data(data))
+            impl_command_for_struct(ident, name, &description, localizations, attrs.as_slice(), permission_methods, options_for_struct_data(data))
         }
         syn::Data::Enum(ref data) => {
-            impl_command_for_enum(ident, name, &description, subcommands_for_enum(data))
+            impl_command_for_enum(ident, name, &description, localizations, attrs.as_slice(), permission_methods, subcommands_for_enum(data))
         }
         _ => abort!(ident, "Can only derive Command for structs (regular commands) or enums (commands with subcommands)"),
     }
 }
 
 #[proc_macro_error]
-#[proc_macro_derive(SubCommand, attributes(name, choice, channel_types, min, max))]
+#[proc_macro_derive(SubCommand, attributes(name, choice, channel_types, min, max, autocomplete, name_localized, description_localized))]
 pub fn derive_subcommmand(input: TokenStream) -> TokenStream {
     let DeriveInput {
         ident, data, ..
@@ -62,20 +68,112 @@ pub fn derive_subcommmand(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro_error]
-#[proc_macro_derive(SubCommandGroup, attributes(name))]
+#[proc_macro_derive(SubCommandGroup, attributes(name, name_localized, description_localized, check, cooldown))]
 pub fn derive_subcommmandgroup(input: TokenStream) -> TokenStream {
     let DeriveInput {
-        ident, data, ..
+        ident, data, attrs, ..
     } = parse_macro_input!(input);
 
     match data {
         syn::Data::Enum(ref data) => {
-            impl_subcommandgroup_for_enum(ident, subcommands_for_enum(data))
+            impl_subcommandgroup_for_enum(ident, attrs.as_slice(), subcommands_for_enum(data))
         }
         _ => abort!(ident, "Can only derive SubCommandGroup for enums"),
     }
 }
 
+#[proc_macro_error]
+#[proc_macro_derive(UserCommand, attributes(name, name_localized, default_member_permissions, dm_permission))]
+pub fn derive_user_command(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, data, attrs, ..
+    } = parse_macro_input!(input);
+
+    let name = utility::get_command_name(&ident, attrs.as_slice());
+    match data {
+        syn::Data::Struct(ref data) => {
+            impl_user_command_for_struct(ident, name, attrs.as_slice(), data)
+        }
+        _ => abort!(ident, "Can only derive UserCommand for structs"),
+    }
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(MessageCommand, attributes(name, name_localized, default_member_permissions, dm_permission))]
+pub fn derive_message_command(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, data, attrs, ..
+    } = parse_macro_input!(input);
+
+    let name = utility::get_command_name(&ident, attrs.as_slice());
+    match data {
+        syn::Data::Struct(ref data) => {
+            impl_message_command_for_struct(ident, name, attrs.as_slice(), data)
+        }
+        _ => abort!(ident, "Can only derive MessageCommand for structs"),
+    }
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(Modal, attributes(custom_id, style, min_length, max_length, placeholder, required))]
+pub fn derive_modal(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, data, attrs, ..
+    } = parse_macro_input!(input);
+
+    match data {
+        syn::Data::Struct(ref data) => {
+            impl_modal_for_struct(ident, attrs.as_slice(), data)
+        }
+        _ => abort!(ident, "Can only derive Modal for structs"),
+    }
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(Buttons, attributes(custom_id, style, emoji))]
+pub fn derive_buttons(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, data, attrs, ..
+    } = parse_macro_input!(input);
+
+    match data {
+        syn::Data::Enum(ref data) => {
+            impl_buttons_for_enum(ident, attrs.as_slice(), data)
+        }
+        _ => abort!(ident, "Can only derive Buttons for enums"),
+    }
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(CustomIdCodec, attributes(custom_id, version))]
+pub fn derive_custom_id_codec(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, data, attrs, ..
+    } = parse_macro_input!(input);
+
+    match data {
+        syn::Data::Struct(ref data) => {
+            impl_custom_id_codec_for_struct(ident, attrs.as_slice(), data)
+        }
+        _ => abort!(ident, "Can only derive CustomIdCodec for structs"),
+    }
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(SelectMenu, attributes(custom_id, placeholder, min_values, max_values, label, emoji))]
+pub fn derive_select_menu(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, data, attrs, ..
+    } = parse_macro_input!(input);
+
+    match data {
+        syn::Data::Enum(ref data) => {
+            impl_select_menu_for_enum(ident, attrs.as_slice(), data)
+        }
+        _ => abort!(ident, "Can only derive SelectMenu for enums"),
+    }
+}
+
 #[proc_macro_error]
 #[proc_macro_derive(Commands)]
 pub fn derive_commands(input: TokenStream) -> TokenStream {
@@ -86,7 +184,7 @@ pub fn derive_commands(input: TokenStream) -> TokenStream {
     let (variant_identifier, field_type): (Vec<Ident>, Vec<proc_macro2::TokenStream>) = match data {
         syn::Data::Enum(ref data) => {
             data.variants.iter()
-                .map(|variant| get_commands_variant_info(variant))
+                .map(get_commands_variant_info)
                 .map(|variant_info| (variant_info.variant_identifier, variant_info.field_type))
                 .multiunzip()
         }
@@ -99,9 +197,10 @@ pub fn derive_commands(input: TokenStream) -> TokenStream {
                 _ctx: &serenity::prelude::Context,
                 command: &serenity::model::prelude::application_command::ApplicationCommandInteraction,
             ) -> Result<Self, slashies::ParseError> {
+                // Names are only unique among commands of the same type
                 match command.data.name.as_ref() {
-                    #(name if name == <#field_type as slashies::Command>::name() => Ok(Self::#variant_identifier(<#field_type as slashies::Command>::parse(command)?)),)*
-                    _ => Err(slashies::ParseError::UnknownCommand),
+                    #(name if command.data.kind == <#field_type as slashies::Command>::kind() && name == <#field_type as slashies::Command>::name() => Ok(Self::#variant_identifier(<#field_type as slashies::Command>::parse(command)?)),)*
+                    _ => Err(slashies::ParseError::UnknownCommand { name: command.data.name.clone() }),
                 }
             }
         
@@ -111,9 +210,31 @@ pub fn derive_commands(input: TokenStream) -> TokenStream {
                 command_interaction: &serenity::model::prelude::application_command::ApplicationCommandInteraction,
             ) -> Result<(), slashies::InvocationError> {
                 match self {
-                    #(Self::#variant_identifier(command) => command.invoke(ctx, command_interaction).await,)*
+                    #(Self::#variant_identifier(command) => {
+                        <#field_type as slashies::Command>::run_checks(command, ctx, command_interaction).await?;
+                        let cooldowns = <#field_type as slashies::Command>::cooldowns(command);
+                        slashies::cooldown::enforce(ctx, command_interaction, &cooldowns).await?;
+                        command.invoke(ctx, command_interaction).await
+                    })*
                 }
             }
+
+            async fn autocomplete(
+                ctx: &serenity::prelude::Context,
+                interaction: &serenity::model::interactions::autocomplete::AutocompleteInteraction,
+            ) -> Result<(), slashies::InvocationError> {
+                match interaction.data.name.as_ref() {
+                    #(name if interaction.data.kind == <#field_type as slashies::Command>::kind() && name == <#field_type as slashies::Command>::name() => <#field_type as slashies::Command>::route_autocomplete(ctx, interaction, &interaction.data.options).await,)*
+                    _ => Err(slashies::ParseError::UnknownCommand { name: interaction.data.name.clone() }.into()),
+                }
+            }
+
+            fn register(
+                commands: &mut serenity::builder::CreateApplicationCommands,
+            ) -> &mut serenity::builder::CreateApplicationCommands {
+                commands
+                #(.create_application_command(|command| <#field_type as slashies::Command>::register(command)))*
+            }
         }
     }.into()
 }
@@ -0,0 +1,275 @@
+use proc_macro::TokenStream;
+use proc_macro_error::abort;
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt, Attribute, DataStruct, Field, Ident, Lit, LitInt, LitStr, Meta, MetaNameValue,
+    NestedMeta,
+};
+
+use crate::utility;
+
+/// Discord's limit on the number of text inputs in a modal
+const MAX_INPUTS: usize = 5;
+/// Discord's limit on the length of modal titles and text input labels
+const MAX_LABEL_LENGTH: usize = 45;
+/// Discord's limit on the length of text input placeholders
+const MAX_PLACEHOLDER_LENGTH: usize = 100;
+/// Discord's limit on the length of a text input's value
+const MAX_INPUT_LENGTH: u64 = 4000;
+
+/// For each text input, we need two sections of code:
+/// - parse_struct_item: Parse the field from the submitted value of the text input
+/// - registration_fn: Add the text input to the modal
+struct InputTokenSections {
+    parse_struct_item: proc_macro2::TokenStream,
+    registration_fn: proc_macro2::TokenStream,
+}
+
+fn get_style(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let style = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("style"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) if list.nested.len() == 1 => match list.nested[0] {
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("short") => quote! { Short },
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("paragraph") => {
+                    quote! { Paragraph }
+                }
+                ref style => abort!(
+                    style,
+                    "Unknown text input style. Supported styles are \"short\" and \"paragraph\""
+                ),
+            },
+            _ => abort!(
+                attr,
+                "Invalid \"style\" attribute. Attribute must be of the form #[style(short)] or #[style(paragraph)]"
+            ),
+        })
+        .unwrap_or_else(|| quote! { Short });
+    quote! { serenity::model::interactions::message_component::InputTextStyle::#style }
+}
+
+fn get_length(attrs: &[Attribute], attr_name: &str) -> Option<(LitInt, u64)> {
+    attrs
+        .iter()
+        .find(|attr| attr.path.is_ident(attr_name))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Int(ref length),
+                ..
+            })) => {
+                let value = length
+                    .base10_parse::<u64>()
+                    .unwrap_or_else(|_| abort!(length, "Invalid \"{}\" attribute", attr_name));
+                (length.clone(), value)
+            }
+            _ => abort!(
+                attr,
+                "Invalid \"{}\" attribute. Attribute must be of the form #[{} = 100]",
+                attr_name,
+                attr_name
+            ),
+        })
+}
+
+fn get_length_limits(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let min_length = get_length(attrs, "min_length");
+    let max_length = get_length(attrs, "max_length");
+    if let Some((ref lit, value)) = min_length {
+        if value > MAX_INPUT_LENGTH {
+            abort!(
+                lit,
+                "\"min_length\" must be between 0 and {}",
+                MAX_INPUT_LENGTH
+            );
+        }
+    }
+    if let Some((ref lit, value)) = max_length {
+        if !(1..=MAX_INPUT_LENGTH).contains(&value) {
+            abort!(
+                lit,
+                "\"max_length\" must be between 1 and {}",
+                MAX_INPUT_LENGTH
+            );
+        }
+    }
+    if let (Some((ref min_lit, min)), Some((_, max))) = (&min_length, &max_length) {
+        if min > max {
+            abort!(
+                min_lit,
+                "\"min_length\" must not be greater than \"max_length\""
+            );
+        }
+    }
+    let min_length = min_length.map(|(lit, _)| quote! { .min_length(#lit) });
+    let max_length = max_length.map(|(lit, _)| quote! { .max_length(#lit) });
+    quote! { #min_length #max_length }
+}
+
+fn get_placeholder(attrs: &[Attribute]) -> Option<proc_macro2::TokenStream> {
+    attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("placeholder"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(ref placeholder),
+                ..
+            })) => placeholder.clone(),
+            _ => abort!(
+                attr,
+                "Invalid \"placeholder\" attribute. Attribute must be of the form #[placeholder = \"text\"]"
+            ),
+        })
+        .map(|placeholder: LitStr| {
+            let length = placeholder.value().chars().count();
+            if length > MAX_PLACEHOLDER_LENGTH {
+                abort!(
+                    placeholder,
+                    "Placeholders must be at most {} characters long, but this one is {} characters long",
+                    MAX_PLACEHOLDER_LENGTH,
+                    length
+                );
+            }
+            quote! { .placeholder(#placeholder) }
+        })
+}
+
+fn get_required(
+    attrs: &[Attribute],
+    field_type: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("required"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::Path(_)) => quote! { true },
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Bool(ref required),
+                ..
+            })) => required.to_token_stream(),
+            _ => abort!(
+                attr,
+                "Invalid \"required\" attribute. Attribute must be of the form #[required] or #[required = false]"
+            ),
+        })
+        .unwrap_or_else(|| quote! { <#field_type as slashies::parsable::ParsableTextInput>::is_required() })
+}
+
+fn input_token_sections_from_field(field: &Field) -> InputTokenSections {
+    let field_ident = field.ident.as_ref().unwrap_or_else(|| {
+        abort!(
+            field.ty,
+            "Unnamed struct fields are not supported for modals"
+        )
+    });
+    let custom_id = field_ident.unraw().to_string();
+    let label = utility::get_description(field.attrs.as_slice())
+        .unwrap_or_else(|| {
+            abort!(
+                field_ident,
+                "Text inputs must specify a label via a docstring"
+            )
+        })
+        .trim()
+        .to_owned();
+    utility::validate_label("Labels", &label, MAX_LABEL_LENGTH, field.attrs.as_slice());
+
+    let field_type = field.ty.to_token_stream();
+    let style = get_style(field.attrs.as_slice());
+    let length_limits = get_length_limits(field.attrs.as_slice());
+    let placeholder = get_placeholder(field.attrs.as_slice());
+    let required = get_required(field.attrs.as_slice(), &field_type);
+    InputTokenSections {
+        parse_struct_item: quote! {
+            #field_ident: <#field_type as slashies::parsable::ParsableTextInput>::parse_input(
+                #custom_id,
+                slashies::parsable::text_input_value(interaction, #custom_id),
+            )?,
+        },
+        registration_fn: quote! {
+            |input: &mut serenity::builder::CreateInputText| input
+                .custom_id(#custom_id)
+                .label(#label)
+                .style(#style)
+                .required(#required)
+                #length_limits
+                #placeholder
+        },
+    }
+}
+
+pub fn impl_modal_for_struct(
+    identifier: Ident,
+    attrs: &[Attribute],
+    data: &DataStruct,
+) -> TokenStream {
+    let custom_id = utility::get_custom_id("Modal", &identifier, attrs);
+    let title = utility::get_description(attrs)
+        .unwrap_or_else(|| abort!(identifier, "Modal must specify a title via a docstring"))
+        .trim()
+        .to_owned();
+    utility::validate_label("Modal titles", &title, MAX_LABEL_LENGTH, attrs);
+
+    let fields = match data.fields {
+        syn::Fields::Named(ref fields) => &fields.named,
+        _ => abort!(
+            identifier,
+            "Can only derive Modal for structs with named fields"
+        ),
+    };
+    if fields.is_empty() {
+        abort!(identifier, "Modals must have at least one text input");
+    }
+    if let Some(extra) = fields.iter().nth(MAX_INPUTS) {
+        abort!(extra, "Modals can have at most {} text inputs", MAX_INPUTS);
+    }
+    let (parse_struct_item, registration_fn): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .map(input_token_sections_from_field)
+        .map(|sections| (sections.parse_struct_item, sections.registration_fn))
+        .unzip();
+
+    quote! {
+        impl slashies::Modal for #identifier {
+            fn custom_id() -> String {
+                #custom_id.to_owned()
+            }
+
+            fn parse(interaction: &serenity::model::interactions::modal::ModalSubmitInteraction) -> Result<Self, slashies::ParseError> {
+                if interaction.data.custom_id != Self::custom_id() {
+                    return Err(slashies::ParseError::UnknownComponent {
+                        custom_id: interaction.data.custom_id.clone(),
+                    });
+                }
+                Ok(Self {
+                    #(#parse_struct_item)*
+                })
+            }
+
+            fn create<'a, 'b>(
+                response: &'a mut serenity::builder::CreateInteractionResponse<'b>,
+            ) -> &'a mut serenity::builder::CreateInteractionResponse<'b> {
+                response
+                    .kind(serenity::model::interactions::InteractionResponseType::Modal)
+                    .interaction_response_data(|data| {
+                        data.custom_id(Self::custom_id())
+                            .title(#title)
+                            .components(|components| {
+                                components
+                                #(.create_action_row(|row| row.create_input_text(#registration_fn)))*
+                            })
+                    })
+            }
+        }
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn examples_fail_with_correct_error() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/modal/*.rs");
+    }
+}
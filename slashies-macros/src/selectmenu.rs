@@ -0,0 +1,246 @@
+use proc_macro::TokenStream;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{Attribute, DataEnum, Ident, Lit, LitInt, LitStr, Meta, MetaNameValue, Variant};
+
+use crate::utility;
+
+/// Discord's limit on the number of options in a select menu
+const MAX_OPTIONS: usize = 25;
+/// Discord's limit on the length of option labels, values and descriptions
+const MAX_OPTION_LENGTH: usize = 100;
+/// Discord's limit on the length of select menu placeholders
+const MAX_PLACEHOLDER_LENGTH: usize = 150;
+
+/// For each option, we need two sections of code:
+/// - parse_arm: Match the value of the option to its variant
+/// - registration_fn: Add the option to the select menu
+struct OptionTokenSections {
+    parse_arm: proc_macro2::TokenStream,
+    registration_fn: proc_macro2::TokenStream,
+}
+
+fn get_label(variant: &Variant) -> String {
+    let label = match variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("label"))
+    {
+        Some(attr) => match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(ref label),
+                ..
+            })) => label.value(),
+            _ => abort!(
+                attr,
+                "Invalid \"label\" attribute. Attribute must be of the form #[label = \"Label\"]"
+            ),
+        },
+        None => variant.ident.to_string(),
+    };
+    let length = label.chars().count();
+    if label.trim().is_empty() || length > MAX_OPTION_LENGTH {
+        abort!(
+            variant.ident,
+            "Select menu option labels must be between 1 and {} characters long, but this one is {} characters long",
+            MAX_OPTION_LENGTH,
+            length
+        );
+    }
+    label
+}
+
+fn get_placeholder(attrs: &[Attribute]) -> Option<proc_macro2::TokenStream> {
+    attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("placeholder"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(ref placeholder),
+                ..
+            })) => placeholder.clone(),
+            _ => abort!(
+                attr,
+                "Invalid \"placeholder\" attribute. Attribute must be of the form #[placeholder = \"text\"]"
+            ),
+        })
+        .map(|placeholder: LitStr| {
+            let length = placeholder.value().chars().count();
+            if length > MAX_PLACEHOLDER_LENGTH {
+                abort!(
+                    placeholder,
+                    "Placeholders must be at most {} characters long, but this one is {} characters long",
+                    MAX_PLACEHOLDER_LENGTH,
+                    length
+                );
+            }
+            quote! { .placeholder(#placeholder) }
+        })
+}
+
+fn get_value_count(attrs: &[Attribute], attr_name: &str) -> Option<(LitInt, usize)> {
+    attrs
+        .iter()
+        .find(|attr| attr.path.is_ident(attr_name))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Int(ref count),
+                ..
+            })) => {
+                let value = count
+                    .base10_parse::<usize>()
+                    .unwrap_or_else(|_| abort!(count, "Invalid \"{}\" attribute", attr_name));
+                (count.clone(), value)
+            }
+            _ => abort!(
+                attr,
+                "Invalid \"{}\" attribute. Attribute must be of the form #[{} = 1]",
+                attr_name,
+                attr_name
+            ),
+        })
+}
+
+/// Get the limits on how many options can be selected, checking that they are possible given the
+/// number of options in the menu
+fn get_value_limits(attrs: &[Attribute], option_count: usize) -> proc_macro2::TokenStream {
+    let min_values = get_value_count(attrs, "min_values");
+    let max_values = get_value_count(attrs, "max_values");
+    if let Some((ref lit, value)) = min_values {
+        if value > option_count {
+            abort!(
+                lit,
+                "\"min_values\" must be between 0 and the number of options ({})",
+                option_count
+            );
+        }
+    }
+    if let Some((ref lit, value)) = max_values {
+        if !(1..=option_count).contains(&value) {
+            abort!(
+                lit,
+                "\"max_values\" must be between 1 and the number of options ({})",
+                option_count
+            );
+        }
+    }
+    let min = min_values.as_ref().map_or(1, |(_, value)| *value);
+    let max = max_values.as_ref().map_or(1, |(_, value)| *value);
+    if min > max {
+        let lit = min_values.as_ref().map(|(lit, _)| lit);
+        abort!(
+            lit,
+            "\"min_values\" must not be greater than \"max_values\""
+        );
+    }
+    let min_values = min_values.map(|(lit, _)| quote! { .min_values(#lit) });
+    let max_values = max_values.map(|(lit, _)| quote! { .max_values(#lit) });
+    quote! { #min_values #max_values }
+}
+
+fn option_token_sections_from_variant(variant: &Variant) -> OptionTokenSections {
+    let variant_ident = &variant.ident;
+    if !matches!(variant.fields, syn::Fields::Unit) {
+        abort!(
+            variant.fields,
+            "Select menu options can't have fields - use a unit variant for each option"
+        );
+    }
+    let value = variant_ident.to_string();
+    let label = get_label(variant);
+    let description = utility::get_description(variant.attrs.as_slice())
+        .unwrap_or_else(|| {
+            abort!(
+                variant_ident,
+                "Select menu options must specify a description via a docstring"
+            )
+        })
+        .trim()
+        .to_owned();
+    utility::validate_label(
+        "Select menu option descriptions",
+        &description,
+        MAX_OPTION_LENGTH,
+        variant.attrs.as_slice(),
+    );
+    let emoji = utility::get_emoji(variant.attrs.as_slice());
+
+    OptionTokenSections {
+        parse_arm: quote! {
+            #value => Ok(Self::#variant_ident),
+        },
+        registration_fn: quote! {
+            |option: &mut serenity::builder::CreateSelectMenuOption| option
+                .label(#label)
+                .value(#value)
+                .description(#description)
+                #emoji
+        },
+    }
+}
+
+pub fn impl_select_menu_for_enum(
+    identifier: Ident,
+    attrs: &[Attribute],
+    data: &DataEnum,
+) -> TokenStream {
+    let custom_id = utility::get_custom_id("Select menu", &identifier, attrs);
+    if data.variants.is_empty() {
+        abort!(identifier, "Select menus must have at least one option");
+    }
+    if let Some(extra) = data.variants.iter().nth(MAX_OPTIONS) {
+        abort!(
+            extra,
+            "Select menus can have at most {} options",
+            MAX_OPTIONS
+        );
+    }
+    let placeholder = get_placeholder(attrs);
+    let value_limits = get_value_limits(attrs, data.variants.len());
+    let (parse_arm, registration_fn): (Vec<_>, Vec<_>) = data
+        .variants
+        .iter()
+        .map(option_token_sections_from_variant)
+        .map(|sections| (sections.parse_arm, sections.registration_fn))
+        .unzip();
+
+    quote! {
+        impl slashies::SelectMenu for #identifier {
+            fn custom_id() -> String {
+                #custom_id.to_owned()
+            }
+
+            fn parse_value(value: &str) -> Result<Self, slashies::ParseError> {
+                match value {
+                    #(#parse_arm)*
+                    _ => Err(slashies::ParseError::InvalidSelection {
+                        custom_id: Self::custom_id(),
+                        value: value.to_owned(),
+                    }),
+                }
+            }
+
+            fn create(
+                menu: &mut serenity::builder::CreateSelectMenu,
+            ) -> &mut serenity::builder::CreateSelectMenu {
+                menu.custom_id(Self::custom_id())
+                    #placeholder
+                    #value_limits
+                    .options(|options| {
+                        options
+                        #(.create_option(#registration_fn))*
+                    })
+            }
+        }
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn examples_fail_with_correct_error() {
+        let t = trybuild::TestCases::new();
+        t.compile_fail("tests/selectmenu/*.rs");
+    }
+}
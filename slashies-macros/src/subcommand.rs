@@ -1,21 +1,26 @@
 use itertools::Itertools;
 use proc_macro::TokenStream;
 use proc_macro_error::abort;
-use syn::{Ident, Variant, Meta, Lit, DataEnum};
+use syn::{Attribute, Ident, Variant, Meta, Lit, DataEnum};
 use quote::{quote, ToTokens};
 
-use crate::{command::OptionTokenSections, utility};
+use crate::{
+    command::{impl_autocomplete_for_struct, route_autocomplete_fn, OptionTokenSections},
+    utility,
+};
 
 pub fn impl_subcommand_for_struct(
     identifier: Ident,
     options: Vec<OptionTokenSections>,
 ) -> TokenStream {
-    let (parse_fetch, parse_struct_item, is_required, registration_fn): (
-        Vec<_>,
-        Vec<_>,
-        Vec<_>,
-        Vec<_>,
-    ) = options
+    let (
+        parse_fetch,
+        parse_struct_item,
+        is_required,
+        registration_fn,
+        autocomplete_arm,
+        character_count,
+    ): (Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>) = options
         .into_iter()
         .map(|option| {
             let OptionTokenSections {
@@ -23,18 +28,33 @@ pub fn impl_subcommand_for_struct(
                 parse_struct_item,
                 is_required,
                 registration_fn,
+                autocomplete_arm,
+                character_count,
             } = option;
-            (parse_fetch, parse_struct_item, is_required, registration_fn)
+            (
+                parse_fetch,
+                parse_struct_item,
+                is_required,
+                registration_fn,
+                autocomplete_arm,
+                character_count,
+            )
         })
         .multiunzip();
-    
+    let autocomplete_impl = impl_autocomplete_for_struct(&identifier, autocomplete_arm);
+    let route_autocomplete = route_autocomplete_fn();
+    let localization_imports = utility::localization_imports();
+
     let output = quote!{
+        #[serenity::async_trait]
         impl slashies::SubCommand for #identifier {
+            const CHARACTER_COUNT: usize = 0 #(+ #character_count)*;
+
             fn parse(
                 option: Option<&serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption>,
             ) -> Result<Self, slashies::ParseError> {
                 let options: std::collections::HashMap<String, serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption> = option
-                    .ok_or(slashies::ParseError::MissingOption)?
+                    .ok_or_else(|| slashies::ParseError::missing_option(serenity::model::interactions::application_command::ApplicationCommandOptionType::SubCommand))?
                     .options
                     .iter()
                     .map(|option| (option.name.clone(), option.clone()))
@@ -50,6 +70,7 @@ pub fn impl_subcommand_for_struct(
             fn register_sub_options(
                 option: &mut serenity::builder::CreateApplicationCommandOption,
             ) -> &mut serenity::builder::CreateApplicationCommandOption {
+                #localization_imports
                 // Ensure required options are added first
                 let mut options: Vec<(bool, Box<dyn Fn(&mut serenity::builder::CreateApplicationCommandOption) -> &mut serenity::builder::CreateApplicationCommandOption>)> = vec![
                     #((#is_required, Box::new(#registration_fn)),)*
@@ -66,7 +87,11 @@ pub fn impl_subcommand_for_struct(
                 }
                 option
             }
+
+            #route_autocomplete
         }
+
+        #autocomplete_impl
     }.into();
     output
 }
@@ -76,6 +101,10 @@ pub struct SubCommandTokenSections {
     pub parse_fetch: proc_macro2::TokenStream,
     pub variant_identifier: proc_macro2::Ident,
     pub registration_fn: proc_macro2::TokenStream,
+    pub autocomplete_route: proc_macro2::TokenStream,
+    pub character_count: proc_macro2::TokenStream,
+    pub check_arm: proc_macro2::TokenStream,
+    pub cooldown_arm: proc_macro2::TokenStream,
 }
 
 pub fn subcommand_token_sections_from_enum_variant(variant: &Variant) -> SubCommandTokenSections {
@@ -104,24 +133,84 @@ pub fn subcommand_token_sections_from_enum_variant(variant: &Variant) -> SubComm
                 Meta::NameValue(value) => value.lit,
                 _ => abort!(name_attr, "Invalid \"name\" attribute"),
             };
+            let is_group = variant
+                .attrs
+                .iter()
+                .any(|attr| attr.path.is_ident("subcommandgroup"));
+            let kind = if is_group { "Subcommand group" } else { "Subcommand" };
+            utility::validate_chat_input_name_lit(kind, &subcommand_name);
             let description = utility::get_description(variant.attrs.as_slice())
                 .unwrap_or_else(|| {
                     abort!(
                         variant,
                         "Subcommands must specify a description via a docstring"
                     )
-                });
+                })
+                .trim()
+                .to_owned();
+            utility::validate_description(kind, &description, variant.attrs.as_slice());
+            let own_character_count =
+                utility::literal_length(&subcommand_name) + description.chars().count();
+            let localizations = utility::get_localization_calls(kind, variant.attrs.as_slice());
             let field_type = field.ty.to_token_stream();
-            if variant
-                .attrs
-                .iter()
-                .find(|attr| attr.path.is_ident("subcommandgroup"))
-                .is_some()
-            {
+            let checks = utility::get_check_calls(variant.attrs.as_slice());
+            // Cooldowns are keyed by the path of the subcommand, e.g. "movies recommend", where `path`
+            // is the path of the command or subcommand group the enum is for
+            let cooldowns = utility::get_cooldowns(
+                variant.attrs.as_slice(),
+                quote! { format!("{} {}", path, #subcommand_name) },
+            );
+            if is_group {
+                // The group's own cooldowns would share its path, so they go on the group's type
+                if let Some(cooldown) = variant.attrs.iter().find(|attr| attr.path.is_ident("cooldown")) {
+                    abort!(
+                        cooldown,
+                        "Subcommand group cooldowns must be declared on the type that derives SubCommandGroup"
+                    );
+                }
+            }
+            let (check_arm, cooldown_arm) = if is_group {
+                (
+                    quote! {
+                        Self::#variant_identifier(value) => {
+                            #checks
+                            <#field_type as slashies::SubCommandGroup>::run_checks(value, ctx, command).await?;
+                        }
+                    },
+                    quote! {
+                        Self::#variant_identifier(value) => <#field_type as slashies::SubCommandGroup>::cooldowns(
+                            value,
+                            &format!("{} {}", path, #subcommand_name),
+                        ),
+                    },
+                )
+            } else {
+                (
+                    quote! {
+                        Self::#variant_identifier(_) => {
+                            #checks
+                        }
+                    },
+                    quote! {
+                        Self::#variant_identifier(_) => vec![#(#cooldowns,)*],
+                    },
+                )
+            };
+            let subcommand_trait = if is_group {
+                quote! { slashies::SubCommandGroup }
+            } else {
+                quote! { slashies::SubCommand }
+            };
+            let autocomplete_route = quote! {
+                if option.name == #subcommand_name {
+                    return <#field_type as #subcommand_trait>::route_autocomplete(ctx, interaction, &option.options).await;
+                }
+            };
+            if is_group {
                 SubCommandTokenSections {
                     parse_fetch: quote! {
                         match options.get(#subcommand_name) {
-                            Some(option) => Some(<#field_type as slashies::SubCommandGroup>::parse(Some(option))?),
+                            Some(option) => Some(<#field_type as slashies::SubCommandGroup>::parse(Some(option)).map_err(|error| error.in_option(#subcommand_name))?),
                             None => None,
                         }
                     },
@@ -132,16 +221,23 @@ pub fn subcommand_token_sections_from_enum_variant(variant: &Variant) -> SubComm
                                 .kind(serenity::model::interactions::application_command::ApplicationCommandOptionType::SubCommandGroup)
                                 .name(#subcommand_name)
                                 .description(#description)
+                                #localizations
                                 .required(false);
                             <#field_type as slashies::SubCommandGroup>::register_sub_options(option)
                         }
                     },
+                    autocomplete_route,
+                    character_count: quote! {
+                        (#own_character_count + <#field_type as slashies::SubCommandGroup>::CHARACTER_COUNT)
+                    },
+                    check_arm,
+                    cooldown_arm,
                 }
             } else {
                 SubCommandTokenSections {
                     parse_fetch: quote! {
                         match options.get(#subcommand_name) {
-                            Some(option) => Some(<#field_type as slashies::SubCommand>::parse(Some(option))?),
+                            Some(option) => Some(<#field_type as slashies::SubCommand>::parse(Some(option)).map_err(|error| error.in_option(#subcommand_name))?),
                             None => None,
                         }
                     },
@@ -152,10 +248,17 @@ pub fn subcommand_token_sections_from_enum_variant(variant: &Variant) -> SubComm
                                 .kind(serenity::model::interactions::application_command::ApplicationCommandOptionType::SubCommand)
                                 .name(#subcommand_name)
                                 .description(#description)
+                                #localizations
                                 .required(false);
                             <#field_type as slashies::SubCommand>::register_sub_options(option)
                         }
                     },
+                    autocomplete_route,
+                    character_count: quote! {
+                        (#own_character_count + <#field_type as slashies::SubCommand>::CHARACTER_COUNT)
+                    },
+                    check_arm,
+                    cooldown_arm,
                 }
             }
         }
@@ -166,10 +269,26 @@ pub fn subcommand_token_sections_from_enum_variant(variant: &Variant) -> SubComm
     }
 }
 
+/// Match on whichever subcommand or subcommand group is being invoked, e.g. to evaluate its checks
+pub fn match_variant(arm: Vec<proc_macro2::TokenStream>) -> Option<proc_macro2::TokenStream> {
+    // An empty enum can never be invoked, so there is nothing to match on
+    (!arm.is_empty()).then(|| {
+        quote! {
+            match self {
+                #(#arm)*
+            }
+        }
+    })
+}
+
 pub fn subcommands_for_enum(data: &DataEnum) -> Vec<SubCommandTokenSections> {
+    utility::validate_count(
+        data.variants.iter(),
+        "Commands and subcommand groups can have at most 25 subcommands or subcommand groups",
+    );
     data.variants
         .iter()
-        .map(|variant| subcommand_token_sections_from_enum_variant(variant))
+        .map(subcommand_token_sections_from_enum_variant)
         .collect()
 }
 
@@ -177,33 +296,73 @@ pub fn impl_command_for_enum(
     identifier: Ident,
     name: Lit,
     description: &str,
+    localizations: proc_macro2::TokenStream,
+    attrs: &[Attribute],
+    permission_methods: proc_macro2::TokenStream,
     sub_commands: Vec<SubCommandTokenSections>,
 ) -> TokenStream {
-    let (parse_fetch, variant_identifier, registration_fn): (Vec<_>, Vec<_>, Vec<_>) = sub_commands
+    let (
+        parse_fetch,
+        variant_identifier,
+        registration_fn,
+        autocomplete_route,
+        character_count,
+        check_arm,
+        cooldown_arm,
+    ): (Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>) = sub_commands
         .into_iter()
         .map(|sub_command| {
             let SubCommandTokenSections {
                 parse_fetch,
                 variant_identifier,
                 registration_fn,
+                autocomplete_route,
+                character_count,
+                check_arm,
+                cooldown_arm,
             } = sub_command;
-            (parse_fetch, variant_identifier, registration_fn)
+            (
+                parse_fetch,
+                variant_identifier,
+                registration_fn,
+                autocomplete_route,
+                character_count,
+                check_arm,
+                cooldown_arm,
+            )
         })
         .multiunzip();
+    let autocomplete_impl = impl_autocomplete_for_enum(&identifier, autocomplete_route);
+    let route_autocomplete = route_autocomplete_fn();
+    let own_character_count = utility::literal_length(&name) + description.chars().count();
+    let character_count_check = utility::character_count_check(&identifier);
+    let localization_imports = utility::localization_imports();
+
+    let checks = utility::get_check_calls(attrs);
+    let check_match = match_variant(check_arm);
+    let cooldowns = utility::get_cooldowns(attrs, quote! { path.to_owned() });
+    let cooldown_match = match_variant(cooldown_arm).unwrap_or_else(|| quote! { Vec::new() });
 
     let output = quote! {
+        #[serenity::async_trait]
         impl slashies::Command for #identifier {
+            const CHARACTER_COUNT: usize = #own_character_count #(+ #character_count)*;
+
             fn parse(command: &serenity::model::interactions::application_command::ApplicationCommandInteraction) -> Result<Self, slashies::ParseError> {
-                let options: std::collections::HashMap<String, serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption> = command.data
-                    .options
-                    .iter()
-                    .map(|option| (option.name.clone(), option.clone()))
-                    .collect();
+                let parse = || -> Result<Self, slashies::ParseError> {
+                    let options: std::collections::HashMap<String, serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption> = command.data
+                        .options
+                        .iter()
+                        .map(|option| (option.name.clone(), option.clone()))
+                        .collect();
 
-                #(if let Some(value) = #parse_fetch {
-                    return Ok(Self::#variant_identifier(value));
-                })*
-                Err(slashies::ParseError::MissingOption)
+                    #(if let Some(value) = #parse_fetch {
+                        return Ok(Self::#variant_identifier(value));
+                    })*
+                    Err(slashies::ParseError::missing_option(serenity::model::interactions::application_command::ApplicationCommandOptionType::SubCommand))
+                };
+                // Add the command name to the start of the path of any errors
+                parse().map_err(|error| error.in_option(#name))
             }
 
             fn name() -> String {
@@ -211,12 +370,62 @@ pub fn impl_command_for_enum(
             }
 
             fn register(command: &mut serenity::builder::CreateApplicationCommand) -> &mut serenity::builder::CreateApplicationCommand {
-                command
+                #localization_imports
+                let command = command
                     .name(#name)
                     .description(#description)
-                    #(.create_option(#registration_fn))*
+                    #localizations
+                    #(.create_option(#registration_fn))*;
+                <Self as slashies::Command>::register_permissions(command)
             }
+
+            #permission_methods
+
+            async fn run_checks(
+                &self,
+                ctx: &serenity::prelude::Context,
+                command: &serenity::model::interactions::application_command::ApplicationCommandInteraction,
+            ) -> Result<(), slashies::InvocationError> {
+                #checks
+                #check_match
+                Ok(())
+            }
+
+            #[allow(unused_variables)]
+            fn cooldowns(&self) -> Vec<slashies::cooldown::DeclaredCooldown> {
+                let path: &str = #name;
+                let invoked: Vec<slashies::cooldown::DeclaredCooldown> = #cooldown_match;
+                vec![#(#cooldowns,)*].into_iter().chain(invoked).collect()
+            }
+
+            #route_autocomplete
         }
+
+        #character_count_check
+
+        #autocomplete_impl
     };
     output.into()
+}
+
+/// Route autocomplete interactions down to whichever subcommand (or subcommand group) was used
+pub fn impl_autocomplete_for_enum(
+    identifier: &Ident,
+    autocomplete_route: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    quote! {
+        #[serenity::async_trait]
+        impl slashies::AutocompleteHandler for #identifier {
+            async fn autocomplete(
+                ctx: &serenity::prelude::Context,
+                interaction: &serenity::model::interactions::autocomplete::AutocompleteInteraction,
+                options: &[serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption],
+            ) -> Result<(), slashies::InvocationError> {
+                for option in options {
+                    #(#autocomplete_route)*
+                }
+                Err(slashies::InvocationError::new("no autocomplete handler for the focused option"))
+            }
+        }
+    }
 }
\ No newline at end of file
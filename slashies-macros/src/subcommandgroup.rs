@@ -1,29 +1,64 @@
-use crate::subcommand::SubCommandTokenSections;
+use crate::{
+    command::route_autocomplete_fn,
+    subcommand::{impl_autocomplete_for_enum, match_variant, SubCommandTokenSections},
+    utility,
+};
 use itertools::Itertools;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::Ident;
+use syn::{Attribute, Ident};
 
 pub fn impl_subcommandgroup_for_enum(
     identifier: Ident,
+    attrs: &[Attribute],
     sub_commands: Vec<SubCommandTokenSections>,
 ) -> TokenStream {
-    let (parse_fetch, variant_identifier, registration_fn): (Vec<_>, Vec<_>, Vec<_>) = sub_commands
+    let (
+        parse_fetch,
+        variant_identifier,
+        registration_fn,
+        autocomplete_route,
+        character_count,
+        check_arm,
+        cooldown_arm,
+    ): (Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>, Vec<_>) = sub_commands
         .into_iter()
         .map(|sub_command| {
             let SubCommandTokenSections {
                 parse_fetch,
                 variant_identifier,
                 registration_fn,
+                autocomplete_route,
+                character_count,
+                check_arm,
+                cooldown_arm,
             } = sub_command;
-            (parse_fetch, variant_identifier, registration_fn)
+            (
+                parse_fetch,
+                variant_identifier,
+                registration_fn,
+                autocomplete_route,
+                character_count,
+                check_arm,
+                cooldown_arm,
+            )
         })
         .multiunzip();
+    let autocomplete_impl = impl_autocomplete_for_enum(&identifier, autocomplete_route);
+    let route_autocomplete = route_autocomplete_fn();
+    let localization_imports = utility::localization_imports();
+    let checks = utility::get_check_calls(attrs);
+    let check_match = match_variant(check_arm);
+    let cooldowns = utility::get_cooldowns(attrs, quote! { path.to_owned() });
+    let cooldown_match = match_variant(cooldown_arm).unwrap_or_else(|| quote! { Vec::new() });
     quote! {
+        #[serenity::async_trait]
         impl slashies::SubCommandGroup for #identifier {
+            const CHARACTER_COUNT: usize = 0 #(+ #character_count)*;
+
             fn parse(option: Option<&serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption>) -> Result<Self, slashies::ParseError> {
                 let options: std::collections::HashMap<String, serenity::model::interactions::application_command::ApplicationCommandInteractionDataOption> = option
-                    .ok_or(slashies::ParseError::MissingOption)?
+                    .ok_or_else(|| slashies::ParseError::missing_option(serenity::model::interactions::application_command::ApplicationCommandOptionType::SubCommandGroup))?
                     .options
                     .iter()
                     .map(|option| (option.name.clone(), option.clone()))
@@ -33,15 +68,36 @@ pub fn impl_subcommandgroup_for_enum(
                     return Ok(Self::#variant_identifier(value));
                 })*
 
-                Err(slashies::ParseError::MissingOption)
+                Err(slashies::ParseError::missing_option(serenity::model::interactions::application_command::ApplicationCommandOptionType::SubCommand))
             }
 
             fn register_sub_options(
                 option: &mut serenity::builder::CreateApplicationCommandOption,
             ) -> &mut serenity::builder::CreateApplicationCommandOption {
+                #localization_imports
                 option
                 #(.create_sub_option(#registration_fn))*
             }
+
+            async fn run_checks(
+                &self,
+                ctx: &serenity::prelude::Context,
+                command: &serenity::model::interactions::application_command::ApplicationCommandInteraction,
+            ) -> Result<(), slashies::InvocationError> {
+                #checks
+                #check_match
+                Ok(())
+            }
+
+            #[allow(unused_variables)]
+            fn cooldowns(&self, path: &str) -> Vec<slashies::cooldown::DeclaredCooldown> {
+                let invoked: Vec<slashies::cooldown::DeclaredCooldown> = #cooldown_match;
+                vec![#(#cooldowns,)*].into_iter().chain(invoked).collect()
+            }
+
+            #route_autocomplete
         }
+
+        #autocomplete_impl
     }.into()
 }
@@ -1,45 +1,158 @@
 use proc_macro_error::abort;
-use quote::quote;
-use syn::{Attribute, Lit, Meta, NestedMeta};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt, punctuated::Punctuated, Attribute, Fields, Ident, Lit, LitBool, LitStr, Meta,
+    MetaNameValue, NestedMeta, Path, Token,
+};
 
-pub(crate) fn get_choices(attrs: &[Attribute]) -> Vec<proc_macro2::TokenStream> {
-    attrs
+/// Discord's limit on the number of options, choices, subcommands and subcommand groups
+const MAX_COUNT: usize = 25;
+/// Discord's limit on the length of descriptions
+const MAX_DESCRIPTION_LENGTH: usize = 100;
+/// Discord's limit on the length of choice names and values
+const MAX_CHOICE_LENGTH: usize = 100;
+/// Discord's limit on the combined length of all names, descriptions and choice values of a command
+const MAX_CHARACTER_COUNT: usize = 4000;
+/// Discord's limit on the length of the custom IDs of modals and message components
+pub(crate) const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+pub(crate) fn get_command_name(ident: &Ident, attrs: &[Attribute]) -> Lit {
+    let name_attr = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("name"))
+        .unwrap_or_else(|| {
+            abort!(
+                ident,
+                "Command must specify a name via the \"name\" attribute"
+            )
+        });
+    let name_meta = name_attr
+        .parse_meta()
+        .unwrap_or_else(|_| abort!(name_attr, "Invalid \"name\" attribute"));
+    match name_meta {
+        Meta::NameValue(value) => value.lit,
+        _ => abort!(name_attr, "Invalid \"name\" attribute"),
+    }
+}
+
+/// Get the registration functions for the choices of an option, along with how many characters
+/// each choice counts towards Discord's 4000 character limit for a command
+pub(crate) fn get_choices(attrs: &[Attribute]) -> Vec<(proc_macro2::TokenStream, usize)> {
+    let choice_attrs = attrs
         .iter()
         .filter(|attr| attr.path.is_ident("choice"))
+        .collect::<Vec<_>>();
+    validate_count(
+        choice_attrs.iter(),
+        "Options can have at most 25 choices",
+    );
+    choice_attrs
+        .into_iter()
         .map(|attr| match attr.parse_meta() {
             Ok(meta) => (attr, meta),
             _ => abort!(attr, "Invalid \"choice\" attribute"),
         })
         .map(|(attr, meta)| match meta {
             Meta::List(list) => {
-                let list = list.nested;
+                // Localized names can be given after the name and value, e.g.
+                // choice("Action", "action", name_localized("de", "Aktion"))
+                let (list, localizations): (Vec<_>, Vec<_>) = list.nested.iter().partition(|nested| {
+                    !matches!(nested, NestedMeta::Meta(meta) if meta.path().is_ident("name_localized"))
+                });
+                let localizations = localizations
+                    .into_iter()
+                    .map(|nested| match nested {
+                        NestedMeta::Meta(meta) => parse_localization(meta),
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
                 let (name_meta, value_meta) = match list.len() {
-                    1 => (&list[0], &list[0]),
-                    2 => (&list[0], &list[1]),
+                    1 => (list[0], list[0]),
+                    2 => (list[0], list[1]),
                     _ => abort!(attr, "Invalid \"choices\" attribute. Attribute must be of the form choice(name, value) or choice(value)"),
                 };
                 let name = match name_meta {
                     NestedMeta::Lit(lit) => lit,
                     _ => abort!(attr, "Invalid \"choices\" attribute"),
                 };
-                match value_meta {
-                    NestedMeta::Lit(Lit::Str(ref lit_str)) => quote! {
-                        .add_string_choice(#name, #lit_str)
-                    },
-                    NestedMeta::Lit(Lit::Int(ref lit_int)) => quote! {
-                        .add_int_choice(#name, #lit_int)
-                    },
-                    NestedMeta::Lit(Lit::Float(ref lit_num)) => quote! {
-                        .add_number_choice(#name, #lit_num)
-                    },
-                    _ => abort!(attr, "Invalid \"choices\" attribute - can only have string, integer or number choices"),
+                let name_length = literal_length(name);
+                if !(1..=MAX_CHOICE_LENGTH).contains(&name_length) {
+                    abort!(
+                        name,
+                        "Choice names must be between 1 and {} characters long, but this one is {} characters long",
+                        MAX_CHOICE_LENGTH,
+                        name_length
+                    );
+                }
+                for (_, localized_name) in &localizations {
+                    let length = localized_name.value().chars().count();
+                    if !(1..=MAX_CHOICE_LENGTH).contains(&length) {
+                        abort!(
+                            localized_name,
+                            "Choice names must be between 1 and {} characters long, but this one is {} characters long",
+                            MAX_CHOICE_LENGTH,
+                            length
+                        );
+                    }
+                }
+                let registration = if localizations.is_empty() {
+                    match value_meta {
+                        NestedMeta::Lit(Lit::Str(ref lit_str)) => quote! {
+                            .add_string_choice(#name, #lit_str)
+                        },
+                        NestedMeta::Lit(Lit::Int(ref lit_int)) => quote! {
+                            .add_int_choice(#name, #lit_int)
+                        },
+                        NestedMeta::Lit(Lit::Float(ref lit_num)) => quote! {
+                            .add_number_choice(#name, #lit_num)
+                        },
+                        _ => abort!(attr, "Invalid \"choices\" attribute - can only have string, integer or number choices"),
+                    }
+                } else {
+                    let (locales, localized_names): (Vec<_>, Vec<_>) = localizations.into_iter().unzip();
+                    let localizations = quote! { &[#((#locales, #localized_names)),*] };
+                    match value_meta {
+                        NestedMeta::Lit(Lit::Str(ref lit_str)) => quote! {
+                            .add_string_choice_localized(&#name.to_string(), #lit_str, #localizations)
+                        },
+                        NestedMeta::Lit(Lit::Int(ref lit_int)) => quote! {
+                            .add_int_choice_localized(&#name.to_string(), #lit_int, #localizations)
+                        },
+                        NestedMeta::Lit(Lit::Float(ref lit_num)) => quote! {
+                            .add_number_choice_localized(&#name.to_string(), #lit_num, #localizations)
+                        },
+                        _ => abort!(attr, "Invalid \"choices\" attribute - can only have string, integer or number choices"),
+                    }
+                };
+                let value_length = match value_meta {
+                    NestedMeta::Lit(value) => literal_length(value),
+                    _ => 0,
+                };
+                if value_length > MAX_CHOICE_LENGTH {
+                    abort!(
+                        value_meta,
+                        "Choice values can be at most {} characters long, but this one is {} characters long",
+                        MAX_CHOICE_LENGTH,
+                        value_length
+                    );
                 }
+                (registration, name_length + value_length)
             }
             _ => abort!(attr, "Invalid \"choices\" attribute. Attribute must be of the form choice(name, value) or choice(value)"),
         })
         .collect::<Vec<_>>()
 }
 
+/// The number of characters a literal name or value will take up once registered
+pub(crate) fn literal_length(lit: &Lit) -> usize {
+    match lit {
+        Lit::Str(lit_str) => lit_str.value().chars().count(),
+        Lit::Int(lit_int) => lit_int.base10_digits().len(),
+        Lit::Float(lit_float) => lit_float.base10_digits().len(),
+        _ => lit.to_token_stream().to_string().chars().count(),
+    }
+}
+
 pub(crate) fn get_channel_types(attrs: &[Attribute]) -> Option<proc_macro2::TokenStream> {
     attrs.iter().find(|attr| attr.path.is_ident("channel_types"))
         .map(|attr| match attr.parse_meta() {
@@ -124,3 +237,463 @@ pub(crate) fn get_maximum_value(attrs: &[Attribute]) -> Option<proc_macro2::Toke
             ),
         })
 }
+
+pub(crate) fn get_autocomplete_handler(attrs: &[Attribute]) -> Option<Path> {
+    attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("autocomplete"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(ref handler),
+                ..
+            })) => handler
+                .parse()
+                .unwrap_or_else(|_| abort!(handler, "Invalid autocomplete handler path")),
+            _ => abort!(
+                attr,
+                "Invalid \"autocomplete\" attribute. Attribute must be of the form #[autocomplete = \"path::to::handler\"]"
+            ),
+        })
+}
+
+/// Evaluate the checks given by the `check` attributes, returning early if one fails
+pub(crate) fn get_check_calls(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let checks = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("check"))
+        .flat_map(|attr| {
+            let checks: Punctuated<Path, Token![,]> = attr
+                .parse_args_with(Punctuated::parse_terminated)
+                .unwrap_or_else(|_| {
+                    abort!(
+                        attr,
+                        "Invalid \"check\" attribute. Attribute must be of the form #[check(path::to::Check)]"
+                    )
+                });
+            if checks.is_empty() {
+                abort!(attr, "The \"check\" attribute must list at least one check");
+            }
+            checks
+        });
+    quote! {
+        #(<#checks as slashies::checks::Check>::check(ctx, command).await?;)*
+    }
+}
+
+/// Declare the cooldowns given by the `cooldown` attributes, identified by the item they are
+/// declared on (an expression evaluating to its path) and their position among its cooldowns
+pub(crate) fn get_cooldowns(
+    attrs: &[Attribute],
+    item: proc_macro2::TokenStream,
+) -> Vec<proc_macro2::TokenStream> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("cooldown"))
+        .enumerate()
+        .map(|(index, attr)| {
+            let cooldown = parse_cooldown(attr);
+            quote! {
+                slashies::cooldown::DeclaredCooldown {
+                    item: #item,
+                    index: #index,
+                    cooldown: #cooldown,
+                }
+            }
+        })
+        .collect()
+}
+
+fn parse_cooldown(attr: &Attribute) -> proc_macro2::TokenStream {
+    let invalid = || -> ! {
+        abort!(
+            attr,
+            "Invalid \"cooldown\" attribute. Attribute must be of the form #[cooldown(per = \"user\", rate = 3, window_secs = 60)]"
+        )
+    };
+    let nested = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list.nested,
+        _ => invalid(),
+    };
+    let (mut scope, mut rate, mut window_secs) = (None, None, None);
+    for meta in nested {
+        let name_value = match meta {
+            NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+            _ => invalid(),
+        };
+        match (name_value.path.get_ident().map(Ident::to_string).as_deref(), &name_value.lit) {
+            (Some("per"), Lit::Str(per)) => {
+                scope = Some(match per.value().as_str() {
+                    "user" => quote! { slashies::cooldown::CooldownScope::User },
+                    "guild" => quote! { slashies::cooldown::CooldownScope::Guild },
+                    "channel" => quote! { slashies::cooldown::CooldownScope::Channel },
+                    _ => abort!(per, "Cooldowns can only be per \"user\", \"guild\" or \"channel\""),
+                })
+            }
+            (Some("rate"), Lit::Int(value)) => match value.base10_parse::<u32>() {
+                Ok(value) if value > 0 => rate = Some(value),
+                _ => abort!(value, "The cooldown rate must be a positive number of uses"),
+            },
+            (Some("window_secs"), Lit::Int(value)) => match value.base10_parse::<u64>() {
+                Ok(value) if value > 0 => window_secs = Some(value),
+                _ => abort!(value, "The cooldown window must be a positive number of seconds"),
+            },
+            _ => invalid(),
+        }
+    }
+    match (scope, rate, window_secs) {
+        (Some(scope), Some(rate), Some(window_secs)) => quote! {
+            slashies::cooldown::Cooldown::new(#scope, #rate, std::time::Duration::from_secs(#window_secs))
+        },
+        _ => invalid(),
+    }
+}
+
+/// Implement `default_member_permissions` and `dm_permission` for a command from its attributes
+pub(crate) fn get_permission_methods(attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let default_member_permissions = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("default_member_permissions"))
+        .map(|attr| {
+            let permissions: Punctuated<Ident, Token![|]> = attr
+                .parse_args_with(Punctuated::parse_terminated)
+                .unwrap_or_else(|_| {
+                    abort!(
+                        attr,
+                        "Invalid \"default_member_permissions\" attribute. Attribute must be of the form #[default_member_permissions(PERMISSION_ONE | PERMISSION_TWO)]"
+                    )
+                });
+            let permissions = permissions.into_iter();
+            quote! {
+                fn default_member_permissions() -> Option<serenity::model::permissions::Permissions> {
+                    Some(serenity::model::permissions::Permissions::empty() #(| serenity::model::permissions::Permissions::#permissions)*)
+                }
+            }
+        });
+    let dm_permission = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("dm_permission"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Bool(LitBool { value, .. }),
+                ..
+            })) => quote! {
+                fn dm_permission() -> Option<bool> {
+                    Some(#value)
+                }
+            },
+            _ => abort!(
+                attr,
+                "Invalid \"dm_permission\" attribute. Attribute must be of the form #[dm_permission = false]"
+            ),
+        });
+    quote! {
+        #default_member_permissions
+        #dm_permission
+    }
+}
+
+/// Check that a slash command, subcommand, subcommand group or option name follows Discord's
+/// naming rules for CHAT_INPUT commands: 1-32 characters, lowercase, and only made up of letters,
+/// numbers, '-' and '_'
+pub(crate) fn validate_chat_input_name<T: ToTokens>(kind: &str, name: &str, span: T) {
+    let length = name.chars().count();
+    if !(1..=32).contains(&length) {
+        abort!(
+            span,
+            "{} names must be between 1 and 32 characters long, but \"{}\" is {} characters long",
+            kind,
+            name,
+            length
+        );
+    }
+    if let Some(invalid) = name
+        .chars()
+        .find(|c| !(c.is_alphanumeric() || *c == '-' || *c == '_'))
+    {
+        abort!(
+            span,
+            "{} names may only contain letters, numbers, '-' and '_', but \"{}\" contains {:?}",
+            kind,
+            name,
+            invalid
+        );
+    }
+    if let Some(uppercase) = name.chars().find(|c| c.is_uppercase()) {
+        abort!(
+            span,
+            "{} names must be lowercase, but \"{}\" contains {:?}",
+            kind,
+            name,
+            uppercase
+        );
+    }
+}
+
+/// Check that the value of a `#[name = "..."]` attribute of a slash command or subcommand is a
+/// string literal holding a valid name
+pub(crate) fn validate_chat_input_name_lit(kind: &str, name: &Lit) {
+    match name {
+        Lit::Str(name_str) => validate_chat_input_name(kind, &name_str.value(), name),
+        _ => abort!(name, "{} names must be string literals", kind),
+    }
+}
+
+/// Check that a description (from a docstring) is between 1 and 100 characters long, pointing at
+/// the docstring if it isn't
+pub(crate) fn validate_description(kind: &str, description: &str, attrs: &[Attribute]) {
+    let length = description.chars().count();
+    if description.is_empty() || length > MAX_DESCRIPTION_LENGTH {
+        let doc_attr = attrs.iter().find(|attr| attr.path.is_ident("doc"));
+        abort!(
+            doc_attr,
+            "{} descriptions must be between 1 and {} characters long, but this one is {} characters long",
+            kind,
+            MAX_DESCRIPTION_LENGTH,
+            length
+        );
+    }
+}
+
+/// Check that a modal title or component label (from a docstring) is between 1 and `max_length`
+/// characters long, pointing at the docstring if it isn't
+pub(crate) fn validate_label(kind: &str, label: &str, max_length: usize, attrs: &[Attribute]) {
+    let length = label.chars().count();
+    if label.is_empty() || length > max_length {
+        let doc_attr = attrs.iter().find(|attr| attr.path.is_ident("doc"));
+        abort!(
+            doc_attr,
+            "{} must be between 1 and {} characters long, but this one is {} characters long",
+            kind,
+            max_length,
+            length
+        );
+    }
+}
+
+/// Get the value of the `#[custom_id = "..."]` attribute of a modal or set of components, falling
+/// back to the name of the type
+pub(crate) fn get_custom_id(kind: &str, ident: &Ident, attrs: &[Attribute]) -> String {
+    let custom_id = match attrs.iter().find(|attr| attr.path.is_ident("custom_id")) {
+        Some(attr) => match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(ref custom_id),
+                ..
+            })) => custom_id.value(),
+            _ => abort!(
+                attr,
+                "Invalid \"custom_id\" attribute. Attribute must be of the form #[custom_id = \"my_id\"]"
+            ),
+        },
+        None => ident.unraw().to_string(),
+    };
+    let length = custom_id.chars().count();
+    if !(1..=MAX_CUSTOM_ID_LENGTH).contains(&length) {
+        abort!(
+            ident,
+            "{} custom IDs must be between 1 and {} characters long, but this one is {} characters long",
+            kind,
+            MAX_CUSTOM_ID_LENGTH,
+            length
+        );
+    }
+    custom_id
+}
+
+/// Bind fields to their own names, or to field_0, field_1... for tuple structs and variants
+pub(crate) fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    fields.iter()
+        .enumerate()
+        .map(|(index, field)| field.ident.clone().unwrap_or_else(|| format_ident!("field_{}", index)))
+        .collect()
+}
+
+/// Fail to compile if a custom ID can be too long when all of its fields have a maximum length.
+/// `header_length` is the length of everything before the separator of the first field. This has
+/// to be checked once the compiler knows the maximum lengths of the fields, so it is done in a
+/// constant rather than in the macro.
+pub(crate) fn custom_id_length_check(header_length: usize, field_types: &[proc_macro2::TokenStream], error: &str) -> proc_macro2::TokenStream {
+    quote! {
+        const _: () = {
+            let max_lengths: &[Option<usize>] = &[
+                #(<#field_types as slashies::parsable::CustomIdField>::MAX_LENGTH,)*
+            ];
+            let mut length = #header_length;
+            let mut bounded = true;
+            let mut index = 0;
+            while index < max_lengths.len() {
+                match max_lengths[index] {
+                    Some(max_length) => length += 1 + max_length,
+                    None => bounded = false,
+                }
+                index += 1;
+            }
+            assert!(!bounded || length <= slashies::parsable::MAX_CUSTOM_ID_LENGTH, #error);
+        };
+    }
+}
+
+/// Parse a custom emoji of the form `<:name:id>` (or `<a:name:id>` for animated emojis)
+fn parse_custom_emoji(emoji: &LitStr) -> proc_macro2::TokenStream {
+    let value = emoji.value();
+    let parts = value
+        .strip_prefix('<')
+        .and_then(|value| value.strip_suffix('>'))
+        .map(|value| value.split(':').collect::<Vec<_>>());
+    match parts.as_deref() {
+        Some([animated @ ("" | "a"), name, id]) if !name.is_empty() => {
+            let animated = *animated == "a";
+            let id = id.parse::<u64>().unwrap_or_else(|_| {
+                abort!(emoji, "Invalid custom emoji id \"{}\"", id)
+            });
+            quote! {
+                serenity::model::channel::ReactionType::Custom {
+                    animated: #animated,
+                    id: serenity::model::id::EmojiId(#id),
+                    name: Some(#name.to_owned()),
+                }
+            }
+        }
+        _ => abort!(
+            emoji,
+            "Invalid custom emoji. Custom emojis must be of the form \"<:name:id>\" or \"<a:name:id>\""
+        ),
+    }
+}
+
+/// Get the emoji given by the `#[emoji = "..."]` attribute of a button or select menu option
+pub(crate) fn get_emoji(attrs: &[Attribute]) -> Option<proc_macro2::TokenStream> {
+    attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("emoji"))
+        .map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(ref emoji),
+                ..
+            })) => {
+                let reaction = if emoji.value().starts_with('<') {
+                    parse_custom_emoji(emoji)
+                } else if emoji.value().is_empty() {
+                    abort!(emoji, "Emojis must not be empty")
+                } else {
+                    quote! { serenity::model::channel::ReactionType::Unicode(#emoji.to_owned()) }
+                };
+                quote! { .emoji(#reaction) }
+            }
+            _ => abort!(
+                attr,
+                "Invalid \"emoji\" attribute. Attribute must be of the form #[emoji = \"👍\"]"
+            ),
+        })
+}
+
+/// Check that there are at most 25 items (options, choices, subcommands), pointing at the first
+/// item over the limit if there are more
+pub(crate) fn validate_count<T: ToTokens>(mut items: impl Iterator<Item = T>, message: &str) {
+    if let Some(extra) = items.nth(MAX_COUNT) {
+        abort!(extra, "{}", message);
+    }
+}
+
+/// Fail to compile a command if it goes over Discord's limit of 4000 characters for the combined
+/// names, descriptions and choices of a command. This has to be checked once the compiler knows the
+/// character counts of all subcommands, so it is done in a constant rather than in the macro.
+pub(crate) fn character_count_check(identifier: &Ident) -> proc_macro2::TokenStream {
+    quote! {
+        const _: () = assert!(
+            <#identifier as slashies::Command>::CHARACTER_COUNT <= #MAX_CHARACTER_COUNT,
+            concat!(
+                "Command `",
+                stringify!(#identifier),
+                "` is over Discord's limit of 4000 characters for the combined names, descriptions and choices of a command",
+            ),
+        );
+    }
+}
+
+/// Parse a `name_localized("locale", "text")` or `description_localized("locale", "text")`
+/// attribute (or nested attribute, for choices), checking that the locale is one Discord supports
+pub(crate) fn parse_localization(meta: &Meta) -> (LitStr, LitStr) {
+    let attr_name = meta
+        .path()
+        .get_ident()
+        .map(|ident| ident.to_string())
+        .unwrap_or_default();
+    let (locale, text) = match meta {
+        Meta::List(list) if list.nested.len() == 2 => match (&list.nested[0], &list.nested[1]) {
+            (NestedMeta::Lit(Lit::Str(locale)), NestedMeta::Lit(Lit::Str(text))) => {
+                (locale.clone(), text.clone())
+            }
+            _ => abort!(
+                meta,
+                "Invalid \"{}\" attribute. Attribute must be of the form {}(\"locale\", \"text\")",
+                attr_name,
+                attr_name
+            ),
+        },
+        _ => abort!(
+            meta,
+            "Invalid \"{}\" attribute. Attribute must be of the form {}(\"locale\", \"text\")",
+            attr_name,
+            attr_name
+        ),
+    };
+    if !slashies::localization::LOCALES.contains(&locale.value().as_str()) {
+        abort!(
+            locale,
+            "Unsupported locale \"{}\". Supported locales are: {}",
+            locale.value(),
+            slashies::localization::LOCALES.join(", ")
+        );
+    }
+    (locale, text)
+}
+
+pub(crate) fn get_localizations(attrs: &[Attribute], attr_name: &str) -> Vec<(LitStr, LitStr)> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident(attr_name))
+        .map(|attr| match attr.parse_meta() {
+            Ok(meta) => parse_localization(&meta),
+            _ => abort!(attr, "Invalid \"{}\" attribute", attr_name),
+        })
+        .collect()
+}
+
+/// Get the builder calls to add the localized names and descriptions given by `name_localized`
+/// and `description_localized` attributes, checking them against Discord's rules in the same way
+/// as the default name and description
+///
+/// The calls need the `slashies::localization::Localize` trait to be in scope.
+pub(crate) fn get_localization_calls(kind: &str, attrs: &[Attribute]) -> proc_macro2::TokenStream {
+    let names = get_localizations(attrs, "name_localized");
+    for (_, name) in &names {
+        validate_chat_input_name(kind, &name.value(), name);
+    }
+    let descriptions = get_localizations(attrs, "description_localized");
+    for (_, description) in &descriptions {
+        let length = description.value().chars().count();
+        if !(1..=MAX_DESCRIPTION_LENGTH).contains(&length) {
+            abort!(
+                description,
+                "{} descriptions must be between 1 and {} characters long, but this one is {} characters long",
+                kind,
+                MAX_DESCRIPTION_LENGTH,
+                length
+            );
+        }
+    }
+    let (name_locales, names): (Vec<_>, Vec<_>) = names.into_iter().unzip();
+    let (description_locales, descriptions): (Vec<_>, Vec<_>) = descriptions.into_iter().unzip();
+    quote! {
+        #(.name_localized(#name_locales, #names))*
+        #(.description_localized(#description_locales, #descriptions))*
+    }
+}
+
+/// Bring the traits used to register localizations into scope for generated registration code
+pub(crate) fn localization_imports() -> proc_macro2::TokenStream {
+    quote! {
+        #[allow(unused_imports)]
+        use slashies::localization::{Localize as _, LocalizeChoices as _};
+    }
+}
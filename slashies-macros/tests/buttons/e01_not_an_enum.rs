@@ -0,0 +1,8 @@
+use slashies_macros::Buttons;
+
+#[derive(Buttons)]
+struct BadButtons {
+    count: u64,
+}
+
+fn main() {}
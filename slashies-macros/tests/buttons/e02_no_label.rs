@@ -0,0 +1,10 @@
+use slashies_macros::Buttons;
+
+#[derive(Buttons)]
+enum BadButtons {
+    /// Yes
+    Yes,
+    No,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use slashies_macros::Buttons;
+
+#[derive(Buttons)]
+enum BadButtons {
+    /// This label goes on and on and on, far beyond what fits on any button in Discord's UI
+    Yes,
+}
+
+fn main() {}
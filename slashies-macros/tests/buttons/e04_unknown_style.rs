@@ -0,0 +1,10 @@
+use slashies_macros::Buttons;
+
+#[derive(Buttons)]
+enum BadButtons {
+    /// Yes
+    #[style(link)]
+    Yes,
+}
+
+fn main() {}
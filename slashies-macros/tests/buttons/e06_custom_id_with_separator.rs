@@ -0,0 +1,10 @@
+use slashies_macros::Buttons;
+
+#[derive(Buttons)]
+#[custom_id = "poll:buttons"]
+enum BadButtons {
+    /// Yes
+    Yes,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use slashies_macros::Buttons;
+
+struct Poll;
+
+#[derive(Buttons)]
+enum BadButtons {
+    /// Yes
+    Yes { poll: Poll },
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use serenity::model::id::{ChannelId, GuildId, MessageId, UserId};
+use slashies_macros::Buttons;
+
+#[derive(Buttons)]
+#[custom_id = "leaderboard"]
+enum BadButtons {
+    /// Next page
+    NextPage {
+        guild: GuildId,
+        channel: ChannelId,
+        message: MessageId,
+        user: UserId,
+        page: u64,
+    },
+}
+
+fn main() {}
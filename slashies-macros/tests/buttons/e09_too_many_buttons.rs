@@ -0,0 +1,59 @@
+use slashies_macros::Buttons;
+
+#[derive(Buttons)]
+enum TooManyButtons {
+    /// Button 1
+    Button1,
+    /// Button 2
+    Button2,
+    /// Button 3
+    Button3,
+    /// Button 4
+    Button4,
+    /// Button 5
+    Button5,
+    /// Button 6
+    Button6,
+    /// Button 7
+    Button7,
+    /// Button 8
+    Button8,
+    /// Button 9
+    Button9,
+    /// Button 10
+    Button10,
+    /// Button 11
+    Button11,
+    /// Button 12
+    Button12,
+    /// Button 13
+    Button13,
+    /// Button 14
+    Button14,
+    /// Button 15
+    Button15,
+    /// Button 16
+    Button16,
+    /// Button 17
+    Button17,
+    /// Button 18
+    Button18,
+    /// Button 19
+    Button19,
+    /// Button 20
+    Button20,
+    /// Button 21
+    Button21,
+    /// Button 22
+    Button22,
+    /// Button 23
+    Button23,
+    /// Button 24
+    Button24,
+    /// Button 25
+    Button25,
+    /// Button 26
+    Button26,
+}
+
+fn main() {}
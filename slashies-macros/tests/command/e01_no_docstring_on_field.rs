@@ -2,7 +2,7 @@ use slashies_macros::Command;
 
 /// A command with an invalid field that has no description
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 struct BadCommand {
     bad_field: u64,
     /// Ok field
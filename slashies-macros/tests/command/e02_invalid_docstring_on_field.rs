@@ -2,7 +2,7 @@ use slashies_macros::Command;
 
 /// A command with an invalid field that has an invalid description
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 struct BadCommand {
     #[doc]
     bad_field: u64,
@@ -3,7 +3,7 @@ use slashies::{ApplicationCommandInteractionHandler, InvocationError};
 use serenity::{async_trait, client::Context, model::interactions::application_command::ApplicationCommandInteraction};
 
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 struct BadCommand;
 
 #[async_trait]
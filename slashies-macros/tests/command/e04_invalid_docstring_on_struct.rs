@@ -4,7 +4,7 @@ use serenity::{async_trait, client::Context, model::interactions::application_co
 
 #[doc]
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 struct BadCommand;
 
 #[async_trait]
@@ -2,7 +2,7 @@ use slashies_macros::Command;
 
 /// An invalid command with unnamed fields
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 struct BadCommand(u64, u64);
 
 fn main() {}
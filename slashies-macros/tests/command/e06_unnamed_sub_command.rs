@@ -2,7 +2,7 @@ use slashies_macros::Command;
 
 /// An command with an unnamed subcommand
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 enum BadCommand {
     Sub(SubCommand),
 }
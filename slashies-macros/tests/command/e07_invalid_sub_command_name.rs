@@ -4,7 +4,7 @@ use serenity::{async_trait, client::Context, model::interactions::application_co
 
 /// An command with an invalid subcommand
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 enum BadCommand {
     /// A subcommand with an invalid name
     #[name]
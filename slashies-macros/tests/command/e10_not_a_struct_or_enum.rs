@@ -2,7 +2,7 @@ use slashies_macros::Command;
 
 /// An invalid command (not a struct or enum)
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 union BadCommand {
     x: u64,
 }
@@ -2,10 +2,10 @@ use slashies_macros::Command;
 
 /// An invalid command
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 enum BadCommand {
     /// A variant that doe snot implement SubCommand
-    #[name = "BadSubcommand"]
+    #[name = "bad_subcommand"]
     Sub(SubCommand),
 }
 
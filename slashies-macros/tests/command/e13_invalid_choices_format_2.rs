@@ -2,7 +2,7 @@ use slashies_macros::Command;
 
 /// An invalid command
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 struct BadCommand {
     /// Field with badly formatted choices
     #[choice("x", "y", "z")]
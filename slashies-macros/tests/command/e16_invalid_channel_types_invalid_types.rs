@@ -3,7 +3,7 @@ use slashies_macros::Command;
 
 /// An invalid command
 #[derive(Command)]
-#[name = "BadCommand"]
+#[name = "bad_command"]
 struct BadCommand {
     /// Field with channel types that don't exist
     #[channel_types("telegram", "telepathy")]
@@ -0,0 +1,12 @@
+use slashies_macros::Command;
+
+/// An invalid command
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand {
+    /// Field with badly formatted autocomplete attribute
+    #[autocomplete("handler")]
+    bad_field: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use slashies_macros::Command;
+
+/// A command with spaces in its name
+#[derive(Command)]
+#[name = "set channel"]
+struct BadCommand;
+
+fn main() {}
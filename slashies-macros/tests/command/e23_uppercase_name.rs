@@ -0,0 +1,8 @@
+use slashies_macros::Command;
+
+/// A command with uppercase letters in its name
+#[derive(Command)]
+#[name = "setChannel"]
+struct BadCommand;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use slashies_macros::Command;
+
+/// A command with a name longer than 32 characters
+#[derive(Command)]
+#[name = "a_command_with_a_really_long_name_"]
+struct BadCommand;
+
+fn main() {}
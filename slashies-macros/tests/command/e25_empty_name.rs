@@ -0,0 +1,8 @@
+use slashies_macros::Command;
+
+/// A command with an empty name
+#[derive(Command)]
+#[name = ""]
+struct BadCommand;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use slashies_macros::Command;
+
+/// A command with a name that isn't a string
+#[derive(Command)]
+#[name = 42]
+struct BadCommand;
+
+fn main() {}
@@ -0,0 +1,12 @@
+use slashies_macros::Command;
+
+/// A command with an option with uppercase letters in its name
+#[derive(Command)]
+#[name = "bad_command"]
+#[allow(non_snake_case)]
+struct BadCommand {
+    /// An option with an invalid name
+    badOption: String,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use slashies_macros::{Command, SubCommand};
+
+/// A command with a subcommand with an invalid name
+#[derive(Command)]
+#[name = "bad_command"]
+enum BadCommand {
+    /// A subcommand with an invalid name
+    #[name = "Sub Command"]
+    Sub(SubCommand),
+}
+
+/// A subcommand
+#[derive(SubCommand)]
+struct SubCommand;
+
+fn main() {}
@@ -0,0 +1,17 @@
+use slashies_macros::{Command, SubCommandGroup};
+
+/// A command with a subcommand group with an invalid name
+#[derive(Command)]
+#[name = "bad_command"]
+enum BadCommand {
+    /// A subcommand group with an invalid name
+    #[subcommandgroup]
+    #[name = "group!"]
+    Group(Group),
+}
+
+/// A subcommand group
+#[derive(SubCommandGroup)]
+enum Group {}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use slashies_macros::Command;
+
+/// A description that goes on and on and on, well past the one hundred characters that Discord allows for it
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand;
+
+fn main() {}
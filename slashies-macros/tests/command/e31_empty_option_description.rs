@@ -0,0 +1,11 @@
+use slashies_macros::Command;
+
+/// A command with an option with an empty description
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand {
+    ///
+    option: String,
+}
+
+fn main() {}
@@ -0,0 +1,61 @@
+use slashies_macros::Command;
+
+/// A command with too many options
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand {
+    /// Option 0
+    option_0: String,
+    /// Option 1
+    option_1: String,
+    /// Option 2
+    option_2: String,
+    /// Option 3
+    option_3: String,
+    /// Option 4
+    option_4: String,
+    /// Option 5
+    option_5: String,
+    /// Option 6
+    option_6: String,
+    /// Option 7
+    option_7: String,
+    /// Option 8
+    option_8: String,
+    /// Option 9
+    option_9: String,
+    /// Option 10
+    option_10: String,
+    /// Option 11
+    option_11: String,
+    /// Option 12
+    option_12: String,
+    /// Option 13
+    option_13: String,
+    /// Option 14
+    option_14: String,
+    /// Option 15
+    option_15: String,
+    /// Option 16
+    option_16: String,
+    /// Option 17
+    option_17: String,
+    /// Option 18
+    option_18: String,
+    /// Option 19
+    option_19: String,
+    /// Option 20
+    option_20: String,
+    /// Option 21
+    option_21: String,
+    /// Option 22
+    option_22: String,
+    /// Option 23
+    option_23: String,
+    /// Option 24
+    option_24: String,
+    /// Option 25
+    option_25: String,
+}
+
+fn main() {}
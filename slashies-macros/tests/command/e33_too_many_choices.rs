@@ -0,0 +1,37 @@
+use slashies_macros::Command;
+
+/// A command with an option with too many choices
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand {
+    /// An option with too many choices
+    #[choice("Choice 0")]
+    #[choice("Choice 1")]
+    #[choice("Choice 2")]
+    #[choice("Choice 3")]
+    #[choice("Choice 4")]
+    #[choice("Choice 5")]
+    #[choice("Choice 6")]
+    #[choice("Choice 7")]
+    #[choice("Choice 8")]
+    #[choice("Choice 9")]
+    #[choice("Choice 10")]
+    #[choice("Choice 11")]
+    #[choice("Choice 12")]
+    #[choice("Choice 13")]
+    #[choice("Choice 14")]
+    #[choice("Choice 15")]
+    #[choice("Choice 16")]
+    #[choice("Choice 17")]
+    #[choice("Choice 18")]
+    #[choice("Choice 19")]
+    #[choice("Choice 20")]
+    #[choice("Choice 21")]
+    #[choice("Choice 22")]
+    #[choice("Choice 23")]
+    #[choice("Choice 24")]
+    #[choice("Choice 25")]
+    option: String,
+}
+
+fn main() {}
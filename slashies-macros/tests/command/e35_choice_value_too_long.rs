@@ -0,0 +1,12 @@
+use slashies_macros::Command;
+
+/// A command with a choice value that is too long
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand {
+    /// An option with a choice value that is too long
+    #[choice("name", "A description that goes on and on and on, well past the one hundred characters that Discord allows for it")]
+    option: String,
+}
+
+fn main() {}
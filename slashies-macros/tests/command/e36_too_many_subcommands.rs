@@ -0,0 +1,91 @@
+use slashies_macros::{Command, SubCommand};
+
+/// A command with too many subcommands
+#[derive(Command)]
+#[name = "bad_command"]
+enum BadCommand {
+    /// Subcommand 0
+    #[name = "sub_0"]
+    Sub0(SubCommand),
+    /// Subcommand 1
+    #[name = "sub_1"]
+    Sub1(SubCommand),
+    /// Subcommand 2
+    #[name = "sub_2"]
+    Sub2(SubCommand),
+    /// Subcommand 3
+    #[name = "sub_3"]
+    Sub3(SubCommand),
+    /// Subcommand 4
+    #[name = "sub_4"]
+    Sub4(SubCommand),
+    /// Subcommand 5
+    #[name = "sub_5"]
+    Sub5(SubCommand),
+    /// Subcommand 6
+    #[name = "sub_6"]
+    Sub6(SubCommand),
+    /// Subcommand 7
+    #[name = "sub_7"]
+    Sub7(SubCommand),
+    /// Subcommand 8
+    #[name = "sub_8"]
+    Sub8(SubCommand),
+    /// Subcommand 9
+    #[name = "sub_9"]
+    Sub9(SubCommand),
+    /// Subcommand 10
+    #[name = "sub_10"]
+    Sub10(SubCommand),
+    /// Subcommand 11
+    #[name = "sub_11"]
+    Sub11(SubCommand),
+    /// Subcommand 12
+    #[name = "sub_12"]
+    Sub12(SubCommand),
+    /// Subcommand 13
+    #[name = "sub_13"]
+    Sub13(SubCommand),
+    /// Subcommand 14
+    #[name = "sub_14"]
+    Sub14(SubCommand),
+    /// Subcommand 15
+    #[name = "sub_15"]
+    Sub15(SubCommand),
+    /// Subcommand 16
+    #[name = "sub_16"]
+    Sub16(SubCommand),
+    /// Subcommand 17
+    #[name = "sub_17"]
+    Sub17(SubCommand),
+    /// Subcommand 18
+    #[name = "sub_18"]
+    Sub18(SubCommand),
+    /// Subcommand 19
+    #[name = "sub_19"]
+    Sub19(SubCommand),
+    /// Subcommand 20
+    #[name = "sub_20"]
+    Sub20(SubCommand),
+    /// Subcommand 21
+    #[name = "sub_21"]
+    Sub21(SubCommand),
+    /// Subcommand 22
+    #[name = "sub_22"]
+    Sub22(SubCommand),
+    /// Subcommand 23
+    #[name = "sub_23"]
+    Sub23(SubCommand),
+    /// Subcommand 24
+    #[name = "sub_24"]
+    Sub24(SubCommand),
+    /// Subcommand 25
+    #[name = "sub_25"]
+    Sub25(SubCommand),
+}
+
+/// A subcommand
+#[derive(SubCommand)]
+struct SubCommand;
+
+fn main() {}
@@ -0,0 +1,84 @@
+use serenity::{async_trait, client::Context, model::interactions::application_command::ApplicationCommandInteraction};
+use slashies::{ApplicationCommandInteractionHandler, InvocationError};
+use slashies_macros::{Command, SubCommand};
+
+/// A command with more than 4000 characters of names and descriptions
+#[derive(Command)]
+#[name = "bad_command"]
+enum BadCommand {
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    #[name = "sub_0"]
+    Sub0(BigSubCommand),
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    #[name = "sub_1"]
+    Sub1(BigSubCommand),
+}
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for BadCommand {
+    async fn invoke(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        unimplemented!()
+    }
+}
+
+/// A subcommand with lots of options
+#[derive(SubCommand)]
+#[allow(dead_code)]
+struct BigSubCommand {
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_0: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_1: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_2: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_3: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_4: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_5: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_6: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_7: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_8: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_9: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_10: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_11: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_12: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_13: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_14: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_15: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_16: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_17: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_18: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_19: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_20: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_21: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_22: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_23: String,
+    /// A description that is just short enough to fit within the one hundred characters Discord allows
+    option_24: String,
+}
+
+fn main() {}
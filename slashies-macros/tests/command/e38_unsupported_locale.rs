@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command translated into a locale Discord doesn't support
+#[derive(Command)]
+#[name = "bad_command"]
+#[name_localized("xx", "schlecht")]
+struct BadCommand;
+
+fn main() {}
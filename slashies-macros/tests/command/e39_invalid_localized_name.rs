@@ -0,0 +1,12 @@
+use slashies_macros::Command;
+
+/// A command with an option with an invalid translated name
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand {
+    /// An option with an invalid translated name
+    #[name_localized("de", "Falscher Name")]
+    option: String,
+}
+
+fn main() {}
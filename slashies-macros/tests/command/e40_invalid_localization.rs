@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command with a translation missing its locale
+#[derive(Command)]
+#[name = "bad_command"]
+#[description_localized = "Ein schlechter Befehl"]
+struct BadCommand;
+
+fn main() {}
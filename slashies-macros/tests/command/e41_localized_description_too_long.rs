@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command with a translated description that is too long
+#[derive(Command)]
+#[name = "bad_command"]
+#[description_localized("de", "Ein Befehl mit einer übersetzten Beschreibung, die viel zu lang ist, weil sie mehr als hundert Zeichen hat")]
+struct BadCommand;
+
+fn main() {}
@@ -0,0 +1,22 @@
+use slashies_macros::Command;
+use slashies::{ApplicationCommandInteractionHandler, InvocationError};
+use serenity::{async_trait, client::Context, model::interactions::application_command::ApplicationCommandInteraction};
+
+/// A command requiring a permission that doesn't exist
+#[derive(Command)]
+#[name = "bad_command"]
+#[default_member_permissions(MANAGE_ROLES | KICK_EVERYONE)]
+struct BadCommand;
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for BadCommand {
+    async fn invoke(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        unimplemented!()
+    }
+}
+
+fn main() {}
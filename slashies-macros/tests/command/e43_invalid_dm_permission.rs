@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command with a DM permission that isn't a bool
+#[derive(Command)]
+#[name = "bad_command"]
+#[dm_permission = "no"]
+struct BadCommand;
+
+fn main() {}
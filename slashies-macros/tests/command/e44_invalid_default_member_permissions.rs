@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command with permissions in the wrong format
+#[derive(Command)]
+#[name = "bad_command"]
+#[default_member_permissions = "MANAGE_ROLES"]
+struct BadCommand;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command with a check given as a string rather than a path
+#[derive(Command)]
+#[name = "bad_command"]
+#[check = "InGuild"]
+struct BadCommand;
+
+fn main() {}
@@ -0,0 +1,24 @@
+use slashies_macros::Command;
+use slashies::{ApplicationCommandInteractionHandler, InvocationError};
+use serenity::{async_trait, client::Context, model::interactions::application_command::ApplicationCommandInteraction};
+
+struct NotACheck;
+
+/// A command with a check that doesn't implement Check
+#[derive(Command)]
+#[name = "bad_command"]
+#[check(NotACheck)]
+struct BadCommand;
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for BadCommand {
+    async fn invoke(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        unimplemented!()
+    }
+}
+
+fn main() {}
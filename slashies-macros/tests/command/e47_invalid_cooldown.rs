@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command with a cooldown missing its window
+#[derive(Command)]
+#[name = "bad_command"]
+#[cooldown(per = "user", rate = 3)]
+struct BadCommand;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command with a cooldown for an unsupported scope
+#[derive(Command)]
+#[name = "bad_command"]
+#[cooldown(per = "role", rate = 3, window_secs = 60)]
+struct BadCommand;
+
+fn main() {}
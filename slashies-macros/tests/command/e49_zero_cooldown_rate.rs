@@ -0,0 +1,9 @@
+use slashies_macros::Command;
+
+/// A command with a cooldown that can never be used
+#[derive(Command)]
+#[name = "bad_command"]
+#[cooldown(per = "user", rate = 0, window_secs = 60)]
+struct BadCommand;
+
+fn main() {}
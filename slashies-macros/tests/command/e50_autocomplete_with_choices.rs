@@ -0,0 +1,14 @@
+use slashies_macros::Command;
+
+/// An invalid command
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand {
+    /// Field with both autocomplete and choices
+    #[autocomplete = "suggest_genres"]
+    #[choice("Action")]
+    #[choice("Horror")]
+    genre: String,
+}
+
+fn main() {}
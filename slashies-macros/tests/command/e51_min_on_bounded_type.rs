@@ -0,0 +1,27 @@
+use slashies_macros::Command;
+use slashies::{parsable::BoundedInt, ApplicationCommandInteractionHandler, InvocationError};
+use serenity::{async_trait, client::Context, model::interactions::application_command::ApplicationCommandInteraction};
+
+type Stars = BoundedInt<1, 5>;
+
+/// A command with a bounded option that also sets its own minimum
+#[derive(Command)]
+#[name = "bad_command"]
+struct BadCommand {
+    /// A field whose type already sets the range
+    #[min = 0]
+    stars: Option<Stars>,
+}
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for BadCommand {
+    async fn invoke(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        unimplemented!()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use slashies_macros::{Command, SubCommand, SubCommandGroup};
+use slashies::{ApplicationCommandInteractionHandler, InvocationError};
+use serenity::{async_trait, client::Context, model::interactions::application_command::ApplicationCommandInteraction};
+
+/// A command with a cooldown on its subcommand group's variant
+#[derive(Command)]
+#[name = "bad_command"]
+enum BadCommand {
+    /// A subcommand group
+    #[subcommandgroup]
+    #[name = "group"]
+    #[cooldown(per = "user", rate = 3, window_secs = 60)]
+    Group(Group),
+}
+
+#[derive(SubCommandGroup)]
+enum Group {
+    /// A subcommand
+    #[name = "sub"]
+    Sub(Sub),
+}
+
+#[derive(SubCommand)]
+struct Sub;
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for BadCommand {
+    async fn invoke(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        unimplemented!()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+use serenity::async_trait;
+use serenity::model::interactions::application_command::ApplicationCommandInteraction;
+use serenity::prelude::Context;
+use slashies::{ApplicationCommandInteractionHandler, InvocationError};
+use slashies_macros::Command;
+
+/// A description that is exactly one hundred characters long, which is the most that Discord allows!!!!
+#[derive(Debug, Command)]
+#[name = "long_description"]
+struct LongDescriptionCommand {
+    /// An option description that is also exactly one hundred characters long, which Discord allows too!!!!
+    option: String,
+}
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for LongDescriptionCommand {
+    async fn invoke(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        unimplemented!()
+    }
+}
+
+fn main() {
+    // The space after `///` is not part of the description
+    let registered = slashies::manifest::command::<LongDescriptionCommand>();
+    assert_eq!(registered["description"].as_str().unwrap().len(), 100);
+    assert_eq!(
+        registered["options"][0]["description"].as_str().unwrap().len(),
+        100
+    );
+}
@@ -0,0 +1,9 @@
+use slashies_macros::CustomIdCodec;
+
+#[derive(CustomIdCodec)]
+enum BadState {
+    First,
+    Second,
+}
+
+fn main() {}
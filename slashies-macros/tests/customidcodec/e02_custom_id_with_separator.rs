@@ -0,0 +1,9 @@
+use slashies_macros::CustomIdCodec;
+
+#[derive(CustomIdCodec)]
+#[custom_id = "results:page"]
+struct BadState {
+    page: u32,
+}
+
+fn main() {}
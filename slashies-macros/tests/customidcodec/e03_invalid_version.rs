@@ -0,0 +1,9 @@
+use slashies_macros::CustomIdCodec;
+
+#[derive(CustomIdCodec)]
+#[version = "2"]
+struct BadState {
+    page: u32,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use serenity::model::id::{ChannelId, GuildId, MessageId, UserId};
+use slashies_macros::CustomIdCodec;
+
+#[derive(CustomIdCodec)]
+#[custom_id = "leaderboard"]
+struct BadState {
+    guild: GuildId,
+    channel: ChannelId,
+    message: MessageId,
+    user: UserId,
+    page: u64,
+}
+
+fn main() {}
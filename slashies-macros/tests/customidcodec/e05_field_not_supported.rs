@@ -0,0 +1,8 @@
+use slashies_macros::CustomIdCodec;
+
+#[derive(CustomIdCodec)]
+struct BadState {
+    rating: f64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use slashies_macros::MessageCommand;
+
+#[derive(MessageCommand)]
+#[name = "Bad Command"]
+enum BadCommand {
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use slashies_macros::MessageCommand;
+use serenity::async_trait;
+use serenity::prelude::*;
+use serenity::model::prelude::application_command::*;
+use slashies::*;
+
+#[derive(MessageCommand)]
+#[name = "Bad Command"]
+struct BadCommand(String);
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for BadCommand {
+    async fn invoke(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        unimplemented!()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use slashies_macros::MessageCommand;
+
+#[derive(MessageCommand)]
+#[name = "Bad Command"]
+struct BadCommand();
+
+fn main() {}
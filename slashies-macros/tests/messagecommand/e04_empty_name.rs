@@ -0,0 +1,7 @@
+use slashies_macros::MessageCommand;
+
+#[derive(MessageCommand)]
+#[name = ""]
+struct BadCommand;
+
+fn main() {}
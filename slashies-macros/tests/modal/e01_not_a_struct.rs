@@ -0,0 +1,9 @@
+use slashies_macros::Modal;
+
+/// A bad modal
+#[derive(Modal)]
+enum BadModal {
+    One,
+}
+
+fn main() {}
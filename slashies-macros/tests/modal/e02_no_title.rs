@@ -0,0 +1,9 @@
+use slashies_macros::Modal;
+
+#[derive(Modal)]
+struct BadModal {
+    /// Name
+    name: String,
+}
+
+fn main() {}
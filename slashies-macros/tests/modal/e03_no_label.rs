@@ -0,0 +1,9 @@
+use slashies_macros::Modal;
+
+/// A modal with an input missing its label
+#[derive(Modal)]
+struct BadModal {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use slashies_macros::Modal;
+
+/// A modal with a label that is too long
+#[derive(Modal)]
+struct BadModal {
+    /// This label is much too long to fit in a text input
+    name: String,
+}
+
+fn main() {}
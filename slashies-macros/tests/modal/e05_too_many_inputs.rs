@@ -0,0 +1,20 @@
+use slashies_macros::Modal;
+
+/// A modal with too many inputs
+#[derive(Modal)]
+struct BadModal {
+    /// One
+    one: String,
+    /// Two
+    two: String,
+    /// Three
+    three: String,
+    /// Four
+    four: String,
+    /// Five
+    five: String,
+    /// Six
+    six: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use slashies_macros::Modal;
+
+/// A modal with an unknown input style
+#[derive(Modal)]
+struct BadModal {
+    /// Name
+    #[style(long)]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use slashies_macros::Modal;
+
+/// A modal with impossible length limits
+#[derive(Modal)]
+struct BadModal {
+    /// Name
+    #[min_length = 20]
+    #[max_length = 10]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use slashies_macros::Modal;
+
+/// A modal with an input that is too long
+#[derive(Modal)]
+struct BadModal {
+    /// Details
+    #[max_length = 5000]
+    details: String,
+}
+
+fn main() {}
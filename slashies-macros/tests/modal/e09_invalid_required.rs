@@ -0,0 +1,11 @@
+use slashies_macros::Modal;
+
+/// A modal with an invalid required attribute
+#[derive(Modal)]
+struct BadModal {
+    /// Name
+    #[required = "yes"]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use slashies_macros::Modal;
+
+/// A modal with a field that isn't a text input
+#[derive(Modal)]
+struct BadModal {
+    /// Age
+    age: bool,
+}
+
+fn main() {}
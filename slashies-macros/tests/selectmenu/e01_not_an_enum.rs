@@ -0,0 +1,8 @@
+use slashies_macros::SelectMenu;
+
+#[derive(SelectMenu)]
+struct BadMenu {
+    choice: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use slashies_macros::SelectMenu;
+
+#[derive(SelectMenu)]
+enum BadMenu {
+    /// A plain option
+    Plain,
+    /// An option with a field
+    WithField(String),
+}
+
+fn main() {}
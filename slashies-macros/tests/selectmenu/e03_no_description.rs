@@ -0,0 +1,10 @@
+use slashies_macros::SelectMenu;
+
+#[derive(SelectMenu)]
+enum BadMenu {
+    /// Action movies
+    Action,
+    Horror,
+}
+
+fn main() {}
@@ -0,0 +1,59 @@
+use slashies_macros::SelectMenu;
+
+#[derive(SelectMenu)]
+enum BadMenu {
+    /// Option 1
+    Option1,
+    /// Option 2
+    Option2,
+    /// Option 3
+    Option3,
+    /// Option 4
+    Option4,
+    /// Option 5
+    Option5,
+    /// Option 6
+    Option6,
+    /// Option 7
+    Option7,
+    /// Option 8
+    Option8,
+    /// Option 9
+    Option9,
+    /// Option 10
+    Option10,
+    /// Option 11
+    Option11,
+    /// Option 12
+    Option12,
+    /// Option 13
+    Option13,
+    /// Option 14
+    Option14,
+    /// Option 15
+    Option15,
+    /// Option 16
+    Option16,
+    /// Option 17
+    Option17,
+    /// Option 18
+    Option18,
+    /// Option 19
+    Option19,
+    /// Option 20
+    Option20,
+    /// Option 21
+    Option21,
+    /// Option 22
+    Option22,
+    /// Option 23
+    Option23,
+    /// Option 24
+    Option24,
+    /// Option 25
+    Option25,
+    /// Option 26
+    Option26,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use slashies_macros::SelectMenu;
+
+#[derive(SelectMenu)]
+#[min_values = 3]
+#[max_values = 2]
+enum BadMenu {
+    /// Action movies
+    Action,
+    /// Science fiction movies
+    SciFi,
+    /// Horror movies
+    Horror,
+}
+
+fn main() {}
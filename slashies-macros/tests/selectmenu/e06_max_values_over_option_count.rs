@@ -0,0 +1,14 @@
+use slashies_macros::SelectMenu;
+
+#[derive(SelectMenu)]
+#[max_values = 4]
+enum BadMenu {
+    /// Action movies
+    Action,
+    /// Science fiction movies
+    SciFi,
+    /// Horror movies
+    Horror,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use slashies_macros::UserCommand;
+
+#[derive(UserCommand)]
+#[name = "Bad Command"]
+enum BadCommand {
+    One,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use slashies::parsable::UserInput;
+use slashies_macros::UserCommand;
+
+#[derive(UserCommand)]
+struct BadCommand {
+    user: UserInput,
+}
+
+fn main() {}
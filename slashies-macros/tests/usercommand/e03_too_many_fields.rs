@@ -0,0 +1,11 @@
+use slashies::parsable::UserInput;
+use slashies_macros::UserCommand;
+
+#[derive(UserCommand)]
+#[name = "Bad Command"]
+struct BadCommand {
+    user: UserInput,
+    other_user: UserInput,
+}
+
+fn main() {}
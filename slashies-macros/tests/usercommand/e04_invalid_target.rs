@@ -0,0 +1,24 @@
+use slashies_macros::UserCommand;
+use serenity::async_trait;
+use serenity::prelude::*;
+use serenity::model::prelude::application_command::*;
+use slashies::*;
+
+#[derive(UserCommand)]
+#[name = "Bad Command"]
+struct BadCommand {
+    user: String,
+}
+
+#[async_trait]
+impl ApplicationCommandInteractionHandler for BadCommand {
+    async fn invoke(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        unimplemented!()
+    }
+}
+
+fn main() {}
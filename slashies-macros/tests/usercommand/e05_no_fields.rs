@@ -0,0 +1,7 @@
+use slashies_macros::UserCommand;
+
+#[derive(UserCommand)]
+#[name = "Bad Command"]
+struct BadCommand {}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use slashies_macros::UserCommand;
+
+#[derive(UserCommand)]
+#[name = "A User Command Name That Is Far Too Long"]
+struct BadCommand;
+
+fn main() {}
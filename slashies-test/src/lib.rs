@@ -0,0 +1,564 @@
+//! Slashies-test provides a local stand-in for the Discord HTTP API, so that
+//! [`ApplicationCommandInteractionHandler::invoke`](https://docs.rs/slashies/latest/slashies/trait.ApplicationCommandInteractionHandler.html#tymethod.invoke)
+//! implementations and `register_commands!` calls can be tested without the network.
+//!
+//! [`FakeDiscord::start`] binds an HTTP server to a free local port and [`FakeDiscord::context`]
+//! hands out a serenity [`Context`] whose [`Http`] client sends its requests there instead of to
+//! Discord. The fake server understands the interaction callback, followup, message edit and
+//! command registration endpoints and records every request it receives, so tests can assert on the
+//! responses, edits and followups a command sends and on the commands that were registered.
+//!
+//! ```
+//! # use serenity::async_trait;
+//! # use serenity::prelude::*;
+//! # use serenity::model::prelude::*;
+//! # use serenity::model::prelude::application_command::*;
+//! # use slashies::*;
+//! # use slashies::testing::*;
+//! # use slashies_macros::*;
+//! # use slashies_test::FakeDiscord;
+//! /// Greet a user
+//! #[derive(Debug, Command)]
+//! #[name = "greet"]
+//! struct HelloCommand {
+//!     /// The name to greet
+//!     name: String,
+//! }
+//!
+//! #[async_trait]
+//! impl ApplicationCommandInteractionHandler for HelloCommand {
+//!     async fn invoke(
+//!         &self,
+//!         ctx: &Context,
+//!         command: &ApplicationCommandInteraction,
+//!     ) -> Result<(), InvocationError> {
+//!         command
+//!             .create_interaction_response(&ctx.http, |response| {
+//!                 response
+//!                     .kind(InteractionResponseType::ChannelMessageWithSource)
+//!                     .interaction_response_data(|message| {
+//!                         message.content(format!("Hello {}", self.name))
+//!                     })
+//!             })
+//!             .await?;
+//!         command
+//!             .create_followup_message(&ctx.http, |message| message.content("Nice to meet you"))
+//!             .await?;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let discord = FakeDiscord::start().await;
+//! let ctx = discord.context();
+//!
+//! register_commands!(&ctx, None, [HelloCommand])?;
+//! assert_eq!(discord.commands(None)[0]["name"], "greet");
+//!
+//! let interaction = InteractionBuilder::new("greet")
+//!     .option(OptionBuilder::string("name", "ferris"))
+//!     .build();
+//! let command = HelloCommand::parse(&interaction)?;
+//! command.invoke(&ctx, &interaction).await?;
+//!
+//! assert_eq!(discord.interaction_responses()[0]["data"]["content"], "Hello ferris");
+//! assert_eq!(discord.followups()[0]["content"], "Nice to meet you");
+//! # Ok(())
+//! # }
+//! ```
+#![warn(missing_docs)]
+
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    body,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde_json::{json, Value};
+use serenity::{
+    cache::Cache,
+    client::bridge::gateway::ShardMessenger,
+    http::{Http, HttpBuilder},
+    model::id::{GuildId, MessageId},
+    prelude::{Context, RwLock, TypeMap},
+};
+use tokio::sync::oneshot;
+
+/// The application ID the fake server and the clients it hands out use
+///
+/// This matches the application ID of interactions built with `slashies::testing::InteractionBuilder`.
+pub const APPLICATION_ID: u64 = 1;
+
+/// A request received by the fake server
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedRequest {
+    /// The HTTP method of the request
+    pub method: String,
+    /// The path of the request, relative to the API root (e.g. `/interactions/1/token/callback`)
+    pub path: String,
+    /// The JSON body of the request, or [`Value::Null`] if it had none (or it wasn't JSON)
+    pub body: Value,
+}
+
+/// A local stand-in for the Discord HTTP API
+///
+/// The server runs in the background on the current tokio runtime until this value is dropped.
+/// It handles the following endpoints:
+///
+/// | Endpoint                                              | Behaviour                                          |
+/// |-------------------------------------------------------|----------------------------------------------------|
+/// | Interaction callback                                  | Records the response and stores it as the original |
+/// | Get, edit or delete the original interaction response | Edits are merged into the stored original          |
+/// | Create, edit or delete a followup message             | Records the followup or edit                       |
+/// | Global and guild application commands                 | Keeps track of the registered commands             |
+/// | Edit a message in a channel                           | Records the edit                                   |
+///
+/// Any other request is recorded and answered with `404 Not Found`.
+pub struct FakeDiscord {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl FakeDiscord {
+    /// Start the fake server on a free local port
+    ///
+    /// # Panics
+    /// Panics if no local port can be bound, or if called outside of a tokio runtime
+    pub async fn start() -> Self {
+        let state = Arc::new(Mutex::new(State::default()));
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let state = state.clone();
+                    async move { Ok::<_, Infallible>(handle(&state, request).await) }
+                }))
+            }
+        });
+
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+            .expect("Failed to bind fake Discord server")
+            .serve(make_service);
+        let address = server.local_addr();
+        let (shutdown, shutdown_signal) = oneshot::channel();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            shutdown_signal.await.ok();
+        }));
+
+        Self {
+            address,
+            state,
+            shutdown: Some(shutdown),
+        }
+    }
+
+    /// The address the fake server is listening on
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Create an HTTP client that sends its requests to the fake server
+    pub fn http(&self) -> Http {
+        HttpBuilder::new("token")
+            .proxy(format!("http://{}", self.address))
+            .expect("Invalid fake Discord server address")
+            .ratelimiter_disabled(true)
+            .application_id(APPLICATION_ID)
+            .build()
+    }
+
+    /// Create a context whose HTTP client sends its requests to the fake server
+    ///
+    /// The context is not connected to a gateway shard, so messages sent through
+    /// [`Context::shard`] are discarded.
+    pub fn context(&self) -> Context {
+        let (shard_tx, _) = futures::channel::mpsc::unbounded();
+        Context {
+            data: Arc::new(RwLock::new(TypeMap::new())),
+            shard: ShardMessenger::new(shard_tx),
+            shard_id: 0,
+            http: Arc::new(self.http()),
+            cache: Arc::new(Cache::new()),
+        }
+    }
+
+    /// All requests received so far, in the order they were received
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// The bodies of all interaction responses (callbacks) received so far
+    pub fn interaction_responses(&self) -> Vec<Value> {
+        self.bodies(|request| {
+            request.method == "POST" && segments(&request.path).first() == Some(&"interactions")
+        })
+    }
+
+    /// The bodies of all edits to the original interaction response received so far
+    pub fn response_edits(&self) -> Vec<Value> {
+        self.bodies(|request| {
+            request.method == "PATCH" && request.path.ends_with("/messages/@original")
+        })
+    }
+
+    /// The bodies of all followup messages received so far
+    pub fn followups(&self) -> Vec<Value> {
+        self.bodies(|request| {
+            request.method == "POST" && matches!(segments(&request.path)[..], ["webhooks", _, _])
+        })
+    }
+
+    /// The message IDs and bodies of all edits to followup messages received so far
+    pub fn followup_edits(&self) -> Vec<(MessageId, Value)> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.method == "PATCH")
+            .filter_map(|request| match segments(&request.path)[..] {
+                ["webhooks", _, _, "messages", id] => Some((parse_id(id)?, request.body.clone())),
+                _ => None,
+            })
+            .map(|(id, body)| (MessageId(id), body))
+            .collect()
+    }
+
+    /// The message IDs and bodies of all edits to messages in channels (rather than through an
+    /// interaction's webhook) received so far
+    pub fn message_edits(&self) -> Vec<(MessageId, Value)> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.method == "PATCH")
+            .filter_map(|request| match segments(&request.path)[..] {
+                ["channels", _, "messages", id] => Some((parse_id(id)?, request.body.clone())),
+                _ => None,
+            })
+            .map(|(id, body)| (MessageId(id), body))
+            .collect()
+    }
+
+    /// The current state of the original interaction response, with all edits applied
+    ///
+    /// Returns `None` if no message has been sent in response to an interaction, or if the
+    /// original response has been deleted.
+    pub fn original_response(&self) -> Option<Value> {
+        self.state.lock().unwrap().original.clone()
+    }
+
+    /// The commands currently registered globally (for `None`) or in the given guild
+    pub fn commands(&self, guild_id: Option<GuildId>) -> Vec<Value> {
+        self.state
+            .lock()
+            .unwrap()
+            .commands
+            .get(&guild_id.map(|guild_id| guild_id.0))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Forget all recorded requests, responses and registered commands
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = State::default();
+    }
+
+    fn bodies(&self, filter: impl Fn(&RecordedRequest) -> bool) -> Vec<Value> {
+        self.state
+            .lock()
+            .unwrap()
+            .requests
+            .iter()
+            .filter(|request| filter(request))
+            .map(|request| request.body.clone())
+            .collect()
+    }
+}
+
+impl Drop for FakeDiscord {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    requests: Vec<RecordedRequest>,
+    original: Option<Value>,
+    commands: BTreeMap<Option<u64>, Vec<Value>>,
+    next_id: u64,
+}
+
+impl State {
+    fn next_id(&mut self) -> u64 {
+        // Start well above the IDs used by the interaction builder to avoid confusion
+        self.next_id += 1;
+        1000 + self.next_id
+    }
+}
+
+async fn handle(state: &Mutex<State>, request: Request<Body>) -> Response<Body> {
+    let method = request.method().clone();
+    let path = request
+        .uri()
+        .path()
+        .trim_start_matches("/api/v10")
+        .to_owned();
+    let body = match body::to_bytes(request.into_body()).await {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        Err(_) => Value::Null,
+    };
+
+    let mut state = state.lock().unwrap();
+    state.requests.push(RecordedRequest {
+        method: method.to_string(),
+        path: path.clone(),
+        body: body.clone(),
+    });
+
+    match (&method, &segments(&path)[..]) {
+        (&Method::POST, ["interactions", _, _, "callback"]) => {
+            if let Some(data) = response_message(&body) {
+                let id = state.next_id();
+                state.original = Some(message(id, &data));
+            }
+            no_content()
+        }
+        (&Method::GET, ["webhooks", _, _, "messages", "@original"]) => {
+            match state.original.clone() {
+                Some(original) => ok(original),
+                None => not_found(),
+            }
+        }
+        (&Method::PATCH, ["webhooks", _, _, "messages", "@original"]) => {
+            let original = match state.original.take() {
+                Some(original) => original,
+                None => message(state.next_id(), &json!({})),
+            };
+            let edited = merge(original, &body);
+            state.original = Some(edited.clone());
+            ok(edited)
+        }
+        (&Method::DELETE, ["webhooks", _, _, "messages", "@original"]) => {
+            state.original = None;
+            no_content()
+        }
+        (&Method::POST, ["webhooks", _, _]) => {
+            let id = state.next_id();
+            ok(message(id, &body))
+        }
+        (&Method::GET | &Method::PATCH, ["webhooks", _, _, "messages", id]) => match parse_id(id) {
+            Some(id) => ok(message(id, &body)),
+            None => not_found(),
+        },
+        (&Method::DELETE, ["webhooks", _, _, "messages", _]) => no_content(),
+        (&Method::PATCH, ["channels", channel_id, "messages", id]) => {
+            match (parse_id(channel_id), parse_id(id)) {
+                (Some(channel_id), Some(id)) => {
+                    let mut edited = message(id, &body);
+                    edited["channel_id"] = json!(channel_id.to_string());
+                    ok(edited)
+                }
+                _ => not_found(),
+            }
+        }
+        (_, ["applications", _, "commands", rest @ ..]) => {
+            handle_commands(&mut state, &method, None, rest, body)
+        }
+        (_, ["applications", _, "guilds", guild_id, "commands", rest @ ..]) => {
+            match parse_id(guild_id) {
+                Some(guild_id) => handle_commands(&mut state, &method, Some(guild_id), rest, body),
+                None => not_found(),
+            }
+        }
+        _ => not_found(),
+    }
+}
+
+fn handle_commands(
+    state: &mut State,
+    method: &Method,
+    guild_id: Option<u64>,
+    rest: &[&str],
+    body: Value,
+) -> Response<Body> {
+    let existing = state.commands.get(&guild_id).cloned().unwrap_or_default();
+    match (method, rest) {
+        (&Method::GET, []) => ok(Value::Array(existing)),
+        (&Method::PUT, []) => {
+            let definitions = body.as_array().cloned().unwrap_or_default();
+            let commands: Vec<Value> = definitions
+                .into_iter()
+                .map(|definition| {
+                    // Discord keeps the ID of a command that is overwritten by one with the same name
+                    let id = find_by_name(&existing, &definition)
+                        .and_then(command_id)
+                        .unwrap_or_else(|| state.next_id());
+                    command(id, guild_id, definition)
+                })
+                .collect();
+            state.commands.insert(guild_id, commands.clone());
+            ok(Value::Array(commands))
+        }
+        (&Method::POST, []) => {
+            let id = find_by_name(&existing, &body)
+                .and_then(command_id)
+                .unwrap_or_else(|| state.next_id());
+            let created = command(id, guild_id, body);
+            let commands = state.commands.entry(guild_id).or_default();
+            commands.retain(|command| command_id(command) != Some(id));
+            commands.push(created.clone());
+            ok(created)
+        }
+        (_, [id]) => {
+            let id = match parse_id(id) {
+                Some(id) => id,
+                None => return not_found(),
+            };
+            let commands = state.commands.entry(guild_id).or_default();
+            let position = match commands
+                .iter()
+                .position(|command| command_id(command) == Some(id))
+            {
+                Some(position) => position,
+                None => return not_found(),
+            };
+            match *method {
+                Method::GET => ok(commands[position].clone()),
+                Method::PATCH => {
+                    let edited = merge(commands[position].clone(), &body);
+                    commands[position] = edited.clone();
+                    ok(edited)
+                }
+                Method::DELETE => {
+                    commands.remove(position);
+                    no_content()
+                }
+                _ => not_found(),
+            }
+        }
+        _ => not_found(),
+    }
+}
+
+/// Get the message data of an interaction response, if the response sends or updates a message
+fn response_message(response: &Value) -> Option<Value> {
+    // 4: ChannelMessageWithSource, 5: DeferredChannelMessageWithSource, 7: UpdateMessage
+    match response["type"].as_u64() {
+        Some(4 | 5 | 7) => Some(response.get("data").cloned().unwrap_or_else(|| json!({}))),
+        _ => None,
+    }
+}
+
+/// Build a message object as Discord would return it, with the given content
+fn message(id: u64, data: &Value) -> Value {
+    merge(
+        json!({
+            "id": id.to_string(),
+            "channel_id": "1",
+            "author": {
+                "id": APPLICATION_ID.to_string(),
+                "username": "bot",
+                "discriminator": "0000",
+                "avatar": null,
+                "bot": true,
+            },
+            "content": "",
+            "timestamp": "2022-01-01T00:00:00.000Z",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "components": [],
+            "pinned": false,
+            "type": 0,
+            "webhook_id": APPLICATION_ID.to_string(),
+        }),
+        data,
+    )
+}
+
+/// Build an application command object as Discord would return it, from its definition
+fn command(id: u64, guild_id: Option<u64>, definition: Value) -> Value {
+    merge(
+        json!({
+            "id": id.to_string(),
+            "application_id": APPLICATION_ID.to_string(),
+            "guild_id": guild_id.map(|guild_id| guild_id.to_string()),
+            "type": 1,
+            "description": "",
+            "options": [],
+            "version": "1",
+        }),
+        &definition,
+    )
+}
+
+/// Overwrite the fields of `base` with the (non-null) fields of `update`
+fn merge(mut base: Value, update: &Value) -> Value {
+    if let (Some(base_fields), Some(update_fields)) = (base.as_object_mut(), update.as_object()) {
+        for (key, value) in update_fields {
+            if !value.is_null() && !matches!(key.as_str(), "id" | "application_id" | "guild_id") {
+                base_fields.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    base
+}
+
+fn find_by_name<'a>(commands: &'a [Value], definition: &Value) -> Option<&'a Value> {
+    commands
+        .iter()
+        .find(|command| command["name"] == definition["name"])
+}
+
+fn command_id(command: &Value) -> Option<u64> {
+    command["id"].as_str().and_then(parse_id)
+}
+
+fn parse_id(id: &str) -> Option<u64> {
+    id.parse().ok()
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+fn ok(body: Value) -> Response<Body> {
+    json_response(StatusCode::OK, &body)
+}
+
+fn no_content() -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::NO_CONTENT;
+    response
+}
+
+fn not_found() -> Response<Body> {
+    json_response(
+        StatusCode::NOT_FOUND,
+        &json!({ "code": 0, "message": "404: Not Found" }),
+    )
+}
+
+fn json_response(status: StatusCode, body: &Value) -> Response<Body> {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
@@ -0,0 +1,115 @@
+use serenity::model::{
+    id::GuildId,
+    interactions::{application_command::ApplicationCommand, InteractionResponseType},
+};
+use slashies::testing::InteractionBuilder;
+use slashies_test::FakeDiscord;
+
+#[tokio::test]
+async fn records_deferred_response_edits_and_followups() {
+    let discord = FakeDiscord::start().await;
+    let ctx = discord.context();
+    let interaction = InteractionBuilder::new("roll").build();
+
+    interaction
+        .create_interaction_response(&ctx.http, |response| {
+            response.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+        })
+        .await
+        .unwrap();
+    interaction
+        .edit_original_interaction_response(&ctx.http, |response| {
+            response.content("You rolled a 4")
+        })
+        .await
+        .unwrap();
+    let followup = interaction
+        .create_followup_message(&ctx.http, |message| message.content("Rolling again..."))
+        .await
+        .unwrap();
+    interaction
+        .edit_followup_message(&ctx.http, followup.id, |message| {
+            message.content("You rolled a 6")
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(discord.interaction_responses()[0]["type"], 5);
+    assert_eq!(discord.response_edits()[0]["content"], "You rolled a 4");
+    assert_eq!(
+        discord.original_response().unwrap()["content"],
+        "You rolled a 4"
+    );
+    assert_eq!(discord.followups()[0]["content"], "Rolling again...");
+    assert_eq!(
+        discord.followup_edits(),
+        vec![(
+            followup.id,
+            serde_json::json!({ "content": "You rolled a 6" })
+        )]
+    );
+
+    interaction
+        .delete_original_interaction_response(&ctx.http)
+        .await
+        .unwrap();
+    assert_eq!(discord.original_response(), None);
+}
+
+#[tokio::test]
+async fn keeps_track_of_registered_commands() {
+    let discord = FakeDiscord::start().await;
+    let ctx = discord.context();
+    let guild_id = GuildId(42);
+
+    let registered = guild_id
+        .set_application_commands(&ctx.http, |commands| {
+            commands
+                .create_application_command(|command| command.name("ping").description("Ping"))
+                .create_application_command(|command| command.name("pong").description("Pong"))
+        })
+        .await
+        .unwrap();
+    assert_eq!(registered.len(), 2);
+    assert_eq!(discord.commands(Some(guild_id)).len(), 2);
+    assert!(discord.commands(None).is_empty());
+
+    // Overwriting a command with the same name keeps its ID
+    let overwritten = guild_id
+        .set_application_commands(&ctx.http, |commands| {
+            commands.create_application_command(|command| command.name("ping").description("Ping!"))
+        })
+        .await
+        .unwrap();
+    assert_eq!(overwritten[0].id, registered[0].id);
+    assert_eq!(discord.commands(Some(guild_id))[0]["description"], "Ping!");
+
+    guild_id
+        .delete_application_command(&ctx.http, overwritten[0].id)
+        .await
+        .unwrap();
+    assert!(discord.commands(Some(guild_id)).is_empty());
+
+    let global = ApplicationCommand::create_global_application_command(&ctx.http, |command| {
+        command.name("help").description("Get help")
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        ApplicationCommand::get_global_application_commands(&ctx.http)
+            .await
+            .unwrap()[0]
+            .id,
+        global.id
+    );
+}
+
+#[tokio::test]
+async fn unknown_routes_are_not_found() {
+    let discord = FakeDiscord::start().await;
+    let ctx = discord.context();
+
+    let result = ctx.http.get_message(1, 1).await;
+    assert!(result.is_err());
+    assert_eq!(discord.requests()[0].path, "/channels/1/messages/1");
+}
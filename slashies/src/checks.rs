@@ -0,0 +1,181 @@
+use crate::InvocationError;
+use serenity::{
+    async_trait, client::Context,
+    model::interactions::application_command::ApplicationCommandInteraction,
+};
+use std::{error::Error, fmt};
+
+/// A precondition that must hold for a command to be invoked
+///
+/// Default member permissions can be overridden by server admins, so checks are the place to
+/// enforce anything the bot relies on. Add them to commands, subcommands and subcommand groups via
+/// the `check` attribute - they are evaluated in order before the handler is invoked, and the first
+/// one to fail stops the command from being invoked:
+/// ```
+/// # use slashies::*;
+/// # use slashies::checks::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// struct InGuild;
+///
+/// #[async_trait]
+/// impl Check for InGuild {
+///     async fn check(
+///         _ctx: &Context,
+///         command: &ApplicationCommandInteraction,
+///     ) -> Result<(), CheckFailure> {
+///         match command.guild_id {
+///             Some(_) => Ok(()),
+///             None => Err(CheckFailure::new("This command can only be used in a server")),
+///         }
+///     }
+/// }
+///
+/// /// Ban a user
+/// #[derive(Debug, Command)]
+/// #[name = "ban"]
+/// #[check(InGuild)]
+/// struct BanCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for BanCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #        unimplemented!()
+/// #    }
+/// # }
+/// ```
+/// Checks on a subcommand group apply to every subcommand in the group. A failed check is returned
+/// from [`Commands::invoke`](crate::Commands::invoke) as an error, which can be told apart from
+/// other errors via [`InvocationError::check_failure`]:
+/// ```
+/// # use slashies::*;
+/// # use slashies::checks::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// # struct InGuild;
+/// # #[async_trait]
+/// # impl Check for InGuild {
+/// #     async fn check(
+/// #         _ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), CheckFailure> {
+/// #         match command.guild_id {
+/// #             Some(_) => Ok(()),
+/// #             None => Err(CheckFailure::new("This command can only be used in a server")),
+/// #         }
+/// #     }
+/// # }
+/// /// Manage the server
+/// #[derive(Debug, Command, ApplicationCommandInteractionHandler)]
+/// #[name = "admin"]
+/// enum AdminCommand {
+///     /// Manage roles
+///     #[name = "roles"]
+///     #[subcommandgroup]
+///     #[check(InGuild)]
+///     Roles(RolesGroup),
+/// }
+///
+/// #[derive(Debug, SubCommandGroup, ApplicationCommandInteractionHandler)]
+/// enum RolesGroup {
+///     /// List the roles in the server
+///     #[name = "list"]
+///     List(ListRoles),
+/// }
+///
+/// #[derive(Debug, SubCommand)]
+/// struct ListRoles;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for ListRoles {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #        unimplemented!()
+/// #    }
+/// # }
+///
+/// #[derive(Debug, Commands)]
+/// enum BotCommands {
+///     Admin(AdminCommand),
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let discord = FakeDiscord::start().await;
+/// let ctx = discord.context();
+/// let interaction = InteractionBuilder::new("admin")
+///     .direct_message()
+///     .option(OptionBuilder::sub_command_group("roles").option(OptionBuilder::sub_command("list")))
+///     .build();
+///
+/// let error = BotCommands::parse(&ctx, &interaction)
+///     .unwrap()
+///     .invoke(&ctx, &interaction)
+///     .await
+///     .unwrap_err();
+/// assert_eq!(
+///     error.check_failure().map(CheckFailure::reason),
+///     Some("This command can only be used in a server")
+/// );
+/// assert_eq!(error.user_message(), Some("This command can only be used in a server"));
+/// # }
+/// ```
+#[async_trait]
+pub trait Check {
+    /// Decide whether the command may be invoked for this interaction
+    async fn check(
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+    ) -> Result<(), CheckFailure>;
+}
+
+/// The error returned when a [`Check`] fails
+///
+/// When converted into an [`InvocationError`], the reason becomes the
+/// [user message](InvocationError::user_message), so it can be shown to the user (for example in
+/// an ephemeral reply). Use [`InvocationError::check_failure`] to tell failed checks apart from
+/// other errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckFailure {
+    reason: String,
+}
+
+impl CheckFailure {
+    /// Create a failure with a reason that can be shown to the user
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+
+    /// Why the check failed
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl fmt::Display for CheckFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Check failed: {}", self.reason)
+    }
+}
+
+impl Error for CheckFailure {}
+
+impl From<CheckFailure> for InvocationError {
+    fn from(failure: CheckFailure) -> Self {
+        let user_message = failure.reason.clone();
+        Self::new(failure).with_user_message(user_message)
+    }
+}
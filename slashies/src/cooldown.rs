@@ -0,0 +1,501 @@
+use crate::InvocationError;
+use serenity::{
+    async_trait, client::Context,
+    model::interactions::application_command::ApplicationCommandInteraction, prelude::TypeMapKey,
+};
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    error::Error,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Who shares a cooldown bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CooldownScope {
+    /// Each user has their own bucket
+    User,
+    /// Everyone in a guild shares a bucket (in DMs, this falls back to the channel)
+    Guild,
+    /// Everyone in a channel shares a bucket
+    Channel,
+}
+
+impl CooldownScope {
+    fn bucket_id(&self, command: &ApplicationCommandInteraction) -> u64 {
+        match self {
+            Self::User => command.user.id.0,
+            Self::Guild => command
+                .guild_id
+                .map(|guild_id| guild_id.0)
+                .unwrap_or(command.channel_id.0),
+            Self::Channel => command.channel_id.0,
+        }
+    }
+}
+
+impl fmt::Display for CooldownScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User => write!(f, "user"),
+            Self::Guild => write!(f, "guild"),
+            Self::Channel => write!(f, "channel"),
+        }
+    }
+}
+
+/// A limit of how many times a command can be used within a window of time
+///
+/// Usually this is declared via the `cooldown` attribute on a command or subcommand (e.g.
+/// `#[cooldown(per = "user", rate = 3, window_secs = 60)]`), which is enforced before the command
+/// is invoked (after its [checks](crate::checks::Check)). The buckets are kept in the
+/// [`CooldownStorage`] stored under [`CooldownStorageKey`] in the context's data, or in an
+/// [`InMemoryCooldowns`] inserted there if there isn't one:
+/// ```
+/// # use slashies::*;
+/// # use slashies::cooldown::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// # use std::{sync::Arc, time::Duration};
+/// /// Recommend a movie
+/// #[derive(Debug, Command)]
+/// #[name = "recommend"]
+/// #[cooldown(per = "user", rate = 1, window_secs = 60)]
+/// struct RecommendCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for RecommendCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// # #[derive(Debug, Commands)]
+/// # enum BotCommands {
+/// #     Recommend(RecommendCommand),
+/// # }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// ctx.data
+///     .write()
+///     .await
+///     .insert::<CooldownStorageKey>(Arc::new(InMemoryCooldowns::default()));
+///
+/// let interaction = InteractionBuilder::new("recommend").build();
+/// let command = BotCommands::parse(&ctx, &interaction).unwrap();
+/// command.invoke(&ctx, &interaction).await.unwrap();
+///
+/// let error = command.invoke(&ctx, &interaction).await.unwrap_err();
+/// let cooldown = error.cooldown().expect("The command should be on cooldown");
+/// assert_eq!(cooldown.scope, CooldownScope::User);
+/// assert!(cooldown.remaining <= Duration::from_secs(60));
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cooldown {
+    /// Who shares a bucket
+    pub scope: CooldownScope,
+    /// How many times the command can be used within the window
+    pub rate: u32,
+    /// How long each use counts against the bucket for
+    pub window: Duration,
+}
+
+impl Cooldown {
+    /// Create a cooldown allowing `rate` uses per `window` for each bucket of the given scope
+    pub fn new(scope: CooldownScope, rate: u32, window: Duration) -> Self {
+        Self {
+            scope,
+            rate,
+            window,
+        }
+    }
+}
+
+/// A cooldown along with where it was declared, which keeps its buckets apart from those of every
+/// other cooldown
+///
+/// A cooldown declared on a command enum is shared by all of its subcommands, while one declared on
+/// a subcommand only counts uses of that subcommand:
+/// ```
+/// # use slashies::*;
+/// # use slashies::cooldown::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// # #[derive(Debug, SubCommand)]
+/// # struct RecommendCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for RecommendCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// # #[derive(Debug, SubCommand)]
+/// # struct SearchCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for SearchCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// /// Find movies to watch
+/// #[derive(Debug, Command, ApplicationCommandInteractionHandler)]
+/// #[name = "movies"]
+/// #[cooldown(per = "user", rate = 2, window_secs = 60)]
+/// enum MoviesCommand {
+///     /// Recommend a movie
+///     #[name = "recommend"]
+///     #[cooldown(per = "user", rate = 1, window_secs = 60)]
+///     Recommend(RecommendCommand),
+///
+///     /// Search for a movie
+///     #[name = "search"]
+///     Search(SearchCommand),
+/// }
+/// # #[derive(Debug, Commands)]
+/// # enum BotCommands {
+/// #     Movies(MoviesCommand),
+/// # }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// let recommend = InteractionBuilder::new("movies")
+///     .option(OptionBuilder::sub_command("recommend"))
+///     .build();
+/// let search = InteractionBuilder::new("movies")
+///     .option(OptionBuilder::sub_command("search"))
+///     .build();
+/// let invoke = |interaction| {
+///     let ctx = ctx.clone();
+///     async move {
+///         let command = BotCommands::parse(&ctx, &interaction).unwrap();
+///         command.invoke(&ctx, &interaction).await
+///     }
+/// };
+///
+/// // Each cooldown is keyed by the path of the command or subcommand it was declared on
+/// let items = MoviesCommand::Recommend(RecommendCommand)
+///     .cooldowns()
+///     .into_iter()
+///     .map(|declared| declared.item)
+///     .collect::<Vec<_>>();
+/// assert_eq!(items, ["movies", "movies recommend"]);
+///
+/// invoke(recommend.clone()).await.unwrap();
+/// // The subcommand's bucket is full, so this use doesn't count against the command's bucket...
+/// assert!(invoke(recommend).await.is_err());
+/// // ...leaving room for one more use of any of its subcommands
+/// invoke(search.clone()).await.unwrap();
+/// assert!(invoke(search).await.is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredCooldown {
+    /// The path of the command, subcommand group or subcommand the cooldown was declared on, made up
+    /// of their names, e.g. `movies recommend`
+    pub item: String,
+    /// The position of the cooldown among those declared on the item
+    pub index: usize,
+    /// The cooldown itself
+    pub cooldown: Cooldown,
+}
+
+/// Use up a slot in the bucket of each of the cooldowns for this interaction, or return an error
+/// with how long is left until the first full bucket has a free slot
+///
+/// Either a slot is used in every bucket or in none of them, so a full bucket doesn't use up the
+/// others. Each bucket is identified by where its cooldown was declared (see [`DeclaredCooldown`])
+/// along with the user, guild or channel, depending on the scope.
+///
+/// The buckets are kept in the [`CooldownStorage`] stored under [`CooldownStorageKey`] in the
+/// context's data. If there isn't one, an [`InMemoryCooldowns`] is inserted there.
+pub async fn enforce(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    cooldowns: &[DeclaredCooldown],
+) -> Result<(), CooldownError> {
+    if cooldowns.is_empty() {
+        return Ok(());
+    }
+    let buckets = cooldowns
+        .iter()
+        .map(|declared| {
+            let Cooldown {
+                scope,
+                rate,
+                window,
+            } = declared.cooldown;
+            CooldownBucket {
+                key: format!(
+                    "{}#{}:{}:{}",
+                    declared.item,
+                    declared.index,
+                    scope,
+                    scope.bucket_id(command)
+                ),
+                rate,
+                window,
+            }
+        })
+        .collect::<Vec<_>>();
+    storage(ctx)
+        .await
+        .acquire(&buckets)
+        .await
+        .map_err(|(index, remaining)| CooldownError {
+            scope: cooldowns[index].cooldown.scope,
+            remaining,
+        })
+}
+
+/// Get the storage from the context's data, inserting one if there isn't one yet
+async fn storage(ctx: &Context) -> Arc<dyn CooldownStorage> {
+    if let Some(storage) = ctx.data.read().await.get::<CooldownStorageKey>() {
+        return storage.clone();
+    }
+    ctx.data
+        .write()
+        .await
+        .entry::<CooldownStorageKey>()
+        .or_insert_with(|| Arc::new(InMemoryCooldowns::default()))
+        .clone()
+}
+
+/// A bucket that a use of a command counts against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CooldownBucket {
+    /// Identifies the bucket
+    pub key: String,
+    /// How many uses fit in the bucket within the window
+    pub rate: u32,
+    /// How long each use counts against the bucket for
+    pub window: Duration,
+}
+
+/// Where cooldown buckets are kept
+///
+/// [`InMemoryCooldowns`] is enough for a bot running as a single process, but buckets can be kept
+/// elsewhere (e.g. to share them between shards running in separate processes) by implementing this
+/// trait and inserting it into the context's data under [`CooldownStorageKey`].
+#[async_trait]
+pub trait CooldownStorage: Send + Sync {
+    /// Record a use of every bucket if each of them has been used fewer than `rate` times within
+    /// the last `window`. Otherwise, record nothing and return the index of the first full bucket
+    /// along with how long is left until its oldest use expires.
+    async fn acquire(&self, buckets: &[CooldownBucket]) -> Result<(), (usize, Duration)>;
+}
+
+/// The key for the [`CooldownStorage`] used by commands in the context's data
+pub struct CooldownStorageKey;
+
+impl TypeMapKey for CooldownStorageKey {
+    type Value = Arc<dyn CooldownStorage>;
+}
+
+/// Keeps cooldown buckets in memory, recording when each use expires
+#[derive(Debug, Default)]
+pub struct InMemoryCooldowns {
+    state: Mutex<InMemoryState>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryState {
+    /// When each use of a bucket expires, oldest first
+    buckets: HashMap<String, VecDeque<Instant>>,
+    /// When each use expires across all buckets, soonest first, so that expired uses can be
+    /// forgotten without going through every bucket
+    expiries: BinaryHeap<Reverse<(Instant, String)>>,
+}
+
+impl InMemoryState {
+    /// Forget about uses that have expired, along with any buckets left empty
+    fn forget_expired(&mut self, now: Instant) {
+        while let Some(Reverse((expires_at, _))) = self.expiries.peek() {
+            if *expires_at > now {
+                break;
+            }
+            let Some(Reverse((_, key))) = self.expiries.pop() else {
+                break;
+            };
+            if let Entry::Occupied(mut uses) = self.buckets.entry(key) {
+                while uses.get().front().is_some_and(|expiry| *expiry <= now) {
+                    uses.get_mut().pop_front();
+                }
+                if uses.get().is_empty() {
+                    uses.remove();
+                }
+            }
+        }
+    }
+}
+
+impl InMemoryCooldowns {
+    fn acquire_at(
+        &self,
+        buckets: &[CooldownBucket],
+        now: Instant,
+    ) -> Result<(), (usize, Duration)> {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        state.forget_expired(now);
+        for (index, bucket) in buckets.iter().enumerate() {
+            let uses = state.buckets.get(&bucket.key);
+            if uses.map_or(0, VecDeque::len) >= bucket.rate as usize {
+                let next_expiry = uses
+                    .and_then(|uses| uses.front().copied())
+                    .unwrap_or(now + bucket.window);
+                return Err((index, next_expiry.saturating_duration_since(now)));
+            }
+        }
+        for bucket in buckets {
+            let expires_at = now + bucket.window;
+            state
+                .buckets
+                .entry(bucket.key.clone())
+                .or_default()
+                .push_back(expires_at);
+            state
+                .expiries
+                .push(Reverse((expires_at, bucket.key.clone())));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CooldownStorage for InMemoryCooldowns {
+    async fn acquire(&self, buckets: &[CooldownBucket]) -> Result<(), (usize, Duration)> {
+        self.acquire_at(buckets, Instant::now())
+    }
+}
+
+/// The error returned when a command is used while it is on cooldown
+///
+/// When converted into an [`InvocationError`], a message saying how long to wait becomes the
+/// [user message](InvocationError::user_message). Use [`InvocationError::cooldown`] to tell
+/// cooldowns apart from other errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CooldownError {
+    /// The scope of the bucket that is full
+    pub scope: CooldownScope,
+    /// How long until the command can be used again
+    pub remaining: Duration,
+}
+
+impl fmt::Display for CooldownError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Command is on cooldown (per {}) for another {:.1}s",
+            self.scope,
+            self.remaining.as_secs_f64()
+        )
+    }
+}
+
+impl Error for CooldownError {}
+
+impl From<CooldownError> for InvocationError {
+    fn from(error: CooldownError) -> Self {
+        // Round up so that users are never told to wait 0 seconds
+        let seconds = error.remaining.as_secs() + u64::from(error.remaining.subsec_nanos() > 0);
+        let user_message = format!(
+            "This command is on cooldown, try again in {} second{}",
+            seconds,
+            if seconds == 1 { "" } else { "s" }
+        );
+        Self::new(error).with_user_message(user_message)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bucket(key: &str, rate: u32) -> CooldownBucket {
+        CooldownBucket {
+            key: key.to_owned(),
+            rate,
+            window: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn buckets_fill_up_and_expire() {
+        let cooldowns = InMemoryCooldowns::default();
+        let a = [bucket("a", 2)];
+        let start = Instant::now();
+
+        assert_eq!(cooldowns.acquire_at(&a, start), Ok(()));
+        let later = start + Duration::from_secs(10);
+        assert_eq!(cooldowns.acquire_at(&a, later), Ok(()));
+        assert_eq!(
+            cooldowns.acquire_at(&a, later),
+            Err((0, Duration::from_secs(50)))
+        );
+        // Other buckets are unaffected
+        assert_eq!(cooldowns.acquire_at(&[bucket("b", 2)], later), Ok(()));
+
+        // Once the first use expires, there is room for one more
+        let expired = start + Duration::from_secs(60);
+        assert_eq!(cooldowns.acquire_at(&a, expired), Ok(()));
+        assert_eq!(
+            cooldowns.acquire_at(&a, expired),
+            Err((0, Duration::from_secs(10)))
+        );
+    }
+
+    #[test]
+    fn full_buckets_do_not_use_up_the_others() {
+        let cooldowns = InMemoryCooldowns::default();
+        let now = Instant::now();
+
+        assert_eq!(cooldowns.acquire_at(&[bucket("b", 1)], now), Ok(()));
+        let both = [bucket("a", 1), bucket("b", 1)];
+        assert_eq!(
+            cooldowns.acquire_at(&both, now),
+            Err((1, Duration::from_secs(60)))
+        );
+        // "a" wasn't used by the rejected attempt
+        assert_eq!(cooldowns.acquire_at(&[bucket("a", 1)], now), Ok(()));
+    }
+
+    #[test]
+    fn expired_buckets_are_forgotten() {
+        let cooldowns = InMemoryCooldowns::default();
+        let start = Instant::now();
+        for key in ["a", "b", "c"] {
+            assert_eq!(cooldowns.acquire_at(&[bucket(key, 1)], start), Ok(()));
+        }
+
+        let expired = start + Duration::from_secs(60);
+        assert_eq!(cooldowns.acquire_at(&[bucket("d", 1)], expired), Ok(()));
+        let state = cooldowns.state.lock().unwrap();
+        assert_eq!(state.buckets.keys().collect::<Vec<_>>(), vec!["d"]);
+        assert_eq!(state.expiries.len(), 1);
+    }
+}
@@ -0,0 +1,952 @@
+use crate::{
+    middleware::{Dispatcher, Middleware},
+    Buttons, Commands, CustomIdCodec, InvocationError, MessageComponentInteractionHandler,
+    ParseError, SelectMenu,
+};
+use serenity::{
+    async_trait,
+    client::{Context, EventHandler},
+    model::{
+        gateway::Ready,
+        id::GuildId,
+        interactions::{
+            application_command::{ApplicationCommand, ApplicationCommandInteraction},
+            autocomplete::AutocompleteInteraction,
+            message_component::MessageComponentInteraction,
+            modal::ModalSubmitInteraction,
+            Interaction, InteractionResponseType,
+        },
+    },
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Handles the message component interactions (button presses, select menu choices) received by
+/// a [`Framework`]
+#[async_trait]
+pub trait ComponentHandler: Send + Sync {
+    /// Handle the message component interaction
+    async fn handle_component(
+        &self,
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+    ) -> Result<(), InvocationError>;
+}
+
+/// Handles the options selected in a [`SelectMenu`], when routed to by a [`ComponentRouter`]
+#[async_trait]
+pub trait SelectMenuHandler<M: SelectMenu> {
+    /// Handle the selected options
+    async fn handle_selection(
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+        selected: Vec<M>,
+    ) -> Result<(), InvocationError>;
+}
+
+/// Handles clicks of one of a set of [`Buttons`], when routed to by a [`ComponentRouter`]
+#[async_trait]
+pub trait ButtonsHandler<B: Buttons> {
+    /// Handle the click of the given button
+    async fn handle_click(
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+        button: B,
+    ) -> Result<(), InvocationError>;
+}
+
+/// A [`ComponentHandler`] that routes each message component interaction by its custom ID
+///
+/// Interactions can be routed to:
+/// - The type of state stored in their custom ID (see [`CustomIdCodec`]), which is decoded and
+///   then invoked as a [`MessageComponentInteractionHandler`]
+/// - A [`SelectMenuHandler`] for a [`SelectMenu`], given the selected options
+/// - A [`ButtonsHandler`] for a set of [`Buttons`], given the button that was clicked
+///
+/// Interactions with a custom ID that no route knows about, or that can't be parsed, are passed to
+/// the [`Framework`]'s [`ErrorHandler`].
+/// ```
+/// # use slashies::*;
+/// # use slashies::framework::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::channel::Message;
+/// # use serenity::model::interactions::InteractionResponseType;
+/// # use serenity::model::interactions::message_component::MessageComponentInteraction;
+/// #[derive(Debug, CustomIdCodec)]
+/// #[custom_id = "results"]
+/// struct ResultsPage {
+///     genre: String,
+///     page: u32,
+/// }
+///
+/// #[async_trait]
+/// impl MessageComponentInteractionHandler for ResultsPage {
+///     async fn invoke(
+///         &mut self,
+///         ctx: &Context,
+///         interaction: &MessageComponentInteraction,
+///         _original_message: &mut Message,
+///     ) {
+///         let content = format!("Page {} of {} movies", self.page, self.genre);
+///         interaction
+///             .create_interaction_response(&ctx.http, |response| {
+///                 response
+///                     .kind(InteractionResponseType::UpdateMessage)
+///                     .interaction_response_data(|message| message.content(content))
+///             })
+///             .await
+///             .unwrap();
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// // Usually this is passed to `Framework::on_component` rather than being called directly
+/// let router = ComponentRouter::new().route::<ResultsPage>();
+///
+/// let interaction = ComponentInteractionBuilder::new("results:1:Horror:3").build();
+/// router.handle_component(&ctx, &interaction).await.unwrap();
+/// assert_eq!(
+///     discord.interaction_responses()[0]["data"]["content"],
+///     "Page 3 of Horror movies"
+/// );
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ComponentRouter {
+    routes: HashMap<String, Arc<dyn ComponentHandler>>,
+}
+
+impl ComponentRouter {
+    /// Create a router without any routes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route interactions whose custom ID holds the given type of state to it
+    pub fn route<S>(mut self) -> Self
+    where
+        S: CustomIdCodec + MessageComponentInteractionHandler + Send + 'static,
+    {
+        self.routes
+            .insert(S::custom_id(), Arc::new(StateRoute::<S>(PhantomData)));
+        self
+    }
+
+    /// Route selections in the given select menu to the handler
+    pub fn select_menu<M, H>(mut self) -> Self
+    where
+        M: SelectMenu + Send + 'static,
+        H: SelectMenuHandler<M> + 'static,
+    {
+        self.routes.insert(
+            M::custom_id(),
+            Arc::new(SelectMenuRoute::<M, H>(PhantomData)),
+        );
+        self
+    }
+
+    /// Route clicks of any of the given buttons to the handler
+    pub fn buttons<B, H>(mut self) -> Self
+    where
+        B: Buttons + Send + 'static,
+        H: ButtonsHandler<B> + 'static,
+    {
+        self.routes.insert(
+            B::base_custom_id(),
+            Arc::new(ButtonsRoute::<B, H>(PhantomData)),
+        );
+        self
+    }
+}
+
+#[async_trait]
+impl ComponentHandler for ComponentRouter {
+    async fn handle_component(
+        &self,
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+    ) -> Result<(), InvocationError> {
+        let custom_id = &interaction.data.custom_id;
+        let prefix = custom_id.split(':').next().unwrap_or_default();
+        // Select menus are identified by their whole custom ID, while other components only by the
+        // part before their fields
+        let route = self
+            .routes
+            .get(custom_id)
+            .or_else(|| self.routes.get(prefix));
+        match route {
+            Some(route) => route.handle_component(ctx, interaction).await,
+            None => Err(ParseError::UnknownComponent {
+                custom_id: custom_id.clone(),
+            }
+            .into()),
+        }
+    }
+}
+
+/// Decodes and invokes one type of state for a [`ComponentRouter`]
+struct StateRoute<S>(PhantomData<fn() -> S>);
+
+#[async_trait]
+impl<S> ComponentHandler for StateRoute<S>
+where
+    S: CustomIdCodec + MessageComponentInteractionHandler + Send + 'static,
+{
+    async fn handle_component(
+        &self,
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+    ) -> Result<(), InvocationError> {
+        let mut state = S::parse(interaction)?;
+        let mut message = interaction.message.clone();
+        state.invoke(ctx, interaction, &mut message).await;
+        Ok(())
+    }
+}
+
+/// Parses the options selected in a select menu and passes them to a handler for a
+/// [`ComponentRouter`]
+struct SelectMenuRoute<M, H>(PhantomData<fn() -> (M, H)>);
+
+#[async_trait]
+impl<M, H> ComponentHandler for SelectMenuRoute<M, H>
+where
+    M: SelectMenu + Send + 'static,
+    H: SelectMenuHandler<M> + 'static,
+{
+    async fn handle_component(
+        &self,
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+    ) -> Result<(), InvocationError> {
+        let selected = M::parse(interaction)?;
+        H::handle_selection(ctx, interaction, selected).await
+    }
+}
+
+/// Parses the button that was clicked and passes it to a handler for a [`ComponentRouter`]
+struct ButtonsRoute<B, H>(PhantomData<fn() -> (B, H)>);
+
+#[async_trait]
+impl<B, H> ComponentHandler for ButtonsRoute<B, H>
+where
+    B: Buttons + Send + 'static,
+    H: ButtonsHandler<B> + 'static,
+{
+    async fn handle_component(
+        &self,
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+    ) -> Result<(), InvocationError> {
+        let button = B::parse(interaction)?;
+        H::handle_click(ctx, interaction, button).await
+    }
+}
+
+/// Handles the modal submissions received by a [`Framework`]
+#[async_trait]
+pub trait ModalHandler: Send + Sync {
+    /// Handle the modal submission
+    async fn handle_modal(
+        &self,
+        ctx: &Context,
+        interaction: &ModalSubmitInteraction,
+    ) -> Result<(), InvocationError>;
+}
+
+/// What a [`Framework`] was doing when an error occurred
+#[derive(Debug, Clone, Copy)]
+pub enum ErrorContext<'a> {
+    /// Parsing or invoking a command
+    Command(&'a ApplicationCommandInteraction),
+    /// Responding to an autocomplete interaction
+    Autocomplete(&'a AutocompleteInteraction),
+    /// Handling a message component interaction
+    Component(&'a MessageComponentInteraction),
+    /// Handling a modal submission
+    Modal(&'a ModalSubmitInteraction),
+    /// Registering the commands once the bot was ready
+    Registration,
+}
+
+impl fmt::Display for ErrorContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Command(interaction) => write!(f, "command /{}", interaction.data.name),
+            Self::Autocomplete(interaction) => {
+                write!(f, "autocomplete for /{}", interaction.data.name)
+            }
+            Self::Component(interaction) => {
+                write!(f, "component \"{}\"", interaction.data.custom_id)
+            }
+            Self::Modal(interaction) => write!(f, "modal \"{}\"", interaction.data.custom_id),
+            Self::Registration => write!(f, "command registration"),
+        }
+    }
+}
+
+/// Handles the errors that occur in a [`Framework`], such as by letting the user know something
+/// went wrong
+#[async_trait]
+pub trait ErrorHandler: Send + Sync {
+    /// Handle the error
+    async fn handle_error(&self, ctx: &Context, context: ErrorContext<'_>, error: InvocationError);
+}
+
+/// The default [`ErrorHandler`], which replies to the interaction that failed with an ephemeral
+/// message and logs the error with [`tracing`]
+///
+/// The reply is the error's [user message](InvocationError::user_message) (e.g. why a
+/// [check](crate::checks::Check) failed) if it has one, or a generic message otherwise. If the
+/// interaction was already responded to, the reply is sent as a followup message instead.
+pub struct ReplyWithUserMessage;
+
+const GENERIC_ERROR_MESSAGE: &str = "Sorry, something went wrong";
+
+/// Reply to an interaction with an ephemeral message, falling back to a followup message if the
+/// interaction was already responded to (the interaction types share these methods, but not a
+/// trait)
+macro_rules! reply_ephemerally {
+    ($ctx:expr, $interaction:expr, $message:expr) => {{
+        let response = $interaction
+            .create_interaction_response(&$ctx.http, |response| {
+                response
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|data| data.content($message).ephemeral(true))
+            })
+            .await;
+        match response {
+            Ok(()) => Ok(()),
+            Err(_) => $interaction
+                .create_followup_message(&$ctx.http, |followup| {
+                    followup.content($message).ephemeral(true)
+                })
+                .await
+                .map(|_| ()),
+        }
+    }};
+}
+
+#[async_trait]
+impl ErrorHandler for ReplyWithUserMessage {
+    async fn handle_error(&self, ctx: &Context, context: ErrorContext<'_>, error: InvocationError) {
+        tracing::error!("Error in {}: {}", context, error);
+        let message = error.user_message().unwrap_or(GENERIC_ERROR_MESSAGE);
+        let replied = match context {
+            ErrorContext::Command(interaction) => reply_ephemerally!(ctx, interaction, message),
+            ErrorContext::Component(interaction) => reply_ephemerally!(ctx, interaction, message),
+            ErrorContext::Modal(interaction) => reply_ephemerally!(ctx, interaction, message),
+            // There is nothing to reply to
+            ErrorContext::Autocomplete(_) | ErrorContext::Registration => Ok(()),
+        };
+        if let Err(reply_error) = replied {
+            tracing::error!("Failed to reply with the error: {}", reply_error);
+        }
+    }
+}
+
+/// Where a [`Framework`] registers its commands when the bot is ready
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Registration {
+    None,
+    Global,
+    Guild(GuildId),
+}
+
+/// A serenity [`EventHandler`] that does everything needed to run a set of [`Commands`]:
+/// - Registers the commands the first time the bot is ready (see [`Framework::register_globally`]
+///   and [`Framework::register_in_guild`])
+/// - Parses and invokes commands, running any [`Middleware`] around them
+/// - Routes autocomplete interactions to the commands' handlers
+/// - Routes message component interactions and modal submissions to the handlers given by
+///   [`Framework::on_component`] and [`Framework::on_modal`]
+/// - Sends any errors to an [`ErrorHandler`] (by default, [`ReplyWithUserMessage`]) rather than
+///   panicking
+///
+/// ```no_run
+/// # use slashies::*;
+/// # use slashies::framework::Framework;
+/// # use slashies::parsable::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// # /// Greet a user
+/// # #[derive(Debug, Command)]
+/// # #[name = "greet"]
+/// # struct HelloCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HelloCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         unimplemented!()
+/// #     }
+/// # }
+/// #[derive(Debug, Commands)]
+/// enum BotCommands {
+///     Hello(HelloCommand),
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let token = "";
+/// # let application_id = 1;
+/// let framework = Framework::<BotCommands>::new().register_globally();
+/// let mut client = Client::builder(&token, GatewayIntents::empty())
+///     .event_handler(framework)
+///     .application_id(application_id)
+///     .await
+///     .expect("Error creating client");
+/// # }
+/// ```
+///
+/// If the bot needs to handle other events too, call [`Framework::handle_interaction`] and
+/// [`Framework::register_commands`] from your own event handler instead.
+pub struct Framework<C> {
+    dispatcher: Dispatcher<C>,
+    registration: Registration,
+    /// Whether the commands have been registered (or are being registered), as the ready event is
+    /// received again whenever a shard connects
+    registered: AtomicBool,
+    component_handler: Option<Arc<dyn ComponentHandler>>,
+    modal_handler: Option<Arc<dyn ModalHandler>>,
+    error_handler: Arc<dyn ErrorHandler>,
+}
+
+impl<C: Commands + Send + Sync> Framework<C> {
+    /// Create a framework that doesn't register the commands, and replies to errors with
+    /// [`ReplyWithUserMessage`]
+    pub fn new() -> Self {
+        Self {
+            dispatcher: Dispatcher::new(),
+            registration: Registration::None,
+            registered: AtomicBool::new(false),
+            component_handler: None,
+            modal_handler: None,
+            error_handler: Arc::new(ReplyWithUserMessage),
+        }
+    }
+
+    /// Register the commands globally the first time the bot is ready
+    pub fn register_globally(mut self) -> Self {
+        self.registration = Registration::Global;
+        self
+    }
+
+    /// Register the commands in the given guild the first time the bot is ready (useful while
+    /// developing, as guild commands update instantly)
+    pub fn register_in_guild(mut self, guild_id: GuildId) -> Self {
+        self.registration = Registration::Guild(guild_id);
+        self
+    }
+
+    /// Add a middleware to the end of the stack run around every command (see [`Dispatcher`])
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.dispatcher = self.dispatcher.with(middleware);
+        self
+    }
+
+    /// Handle message component interactions with the given handler (such as a
+    /// [`ComponentRouter`] or [`ComponentSessions`](crate::sessions::ComponentSessions))
+    pub fn on_component(mut self, handler: impl ComponentHandler + 'static) -> Self {
+        self.component_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Handle modal submissions with the given handler
+    pub fn on_modal(mut self, handler: impl ModalHandler + 'static) -> Self {
+        self.modal_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Handle errors with the given handler rather than [`ReplyWithUserMessage`]
+    pub fn on_error(mut self, handler: impl ErrorHandler + 'static) -> Self {
+        self.error_handler = Arc::new(handler);
+        self
+    }
+
+    /// Route the interaction to the command, autocomplete, component or modal handler it is for
+    pub async fn handle_interaction(&self, ctx: &Context, interaction: &Interaction) {
+        let (context, result) = match interaction {
+            Interaction::ApplicationCommand(interaction) => (
+                ErrorContext::Command(interaction),
+                self.dispatcher.dispatch(ctx, interaction).await,
+            ),
+            Interaction::Autocomplete(interaction) => (
+                ErrorContext::Autocomplete(interaction),
+                C::autocomplete(ctx, interaction).await,
+            ),
+            Interaction::MessageComponent(interaction) => match &self.component_handler {
+                Some(handler) => (
+                    ErrorContext::Component(interaction),
+                    handler.handle_component(ctx, interaction).await,
+                ),
+                None => return,
+            },
+            Interaction::ModalSubmit(interaction) => match &self.modal_handler {
+                Some(handler) => (
+                    ErrorContext::Modal(interaction),
+                    handler.handle_modal(ctx, interaction).await,
+                ),
+                None => return,
+            },
+            Interaction::Ping(_) => return,
+        };
+        if let Err(error) = result {
+            self.error_handler.handle_error(ctx, context, error).await;
+        }
+    }
+
+    /// Register the commands as configured (or do nothing if the framework wasn't configured to
+    /// register them)
+    pub async fn register_commands(&self, ctx: &Context) -> Result<(), serenity::Error> {
+        match self.registration {
+            Registration::None => return Ok(()),
+            Registration::Global => {
+                ApplicationCommand::set_global_application_commands(&ctx.http, C::register).await?
+            }
+            Registration::Guild(guild_id) => {
+                guild_id
+                    .set_application_commands(&ctx.http, C::register)
+                    .await?
+            }
+        };
+        Ok(())
+    }
+
+    /// Register the commands unless they have already been registered by an earlier call, passing
+    /// any errors to the error handler
+    ///
+    /// If registering fails, the next call tries again.
+    async fn register_commands_once(&self, ctx: &Context) {
+        if self.registered.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        if let Err(error) = self.register_commands(ctx).await {
+            self.registered.store(false, Ordering::SeqCst);
+            self.error_handler
+                .handle_error(ctx, ErrorContext::Registration, error.into())
+                .await;
+        }
+    }
+}
+
+impl<C: Commands + Send + Sync> Default for Framework<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<C: Commands + Send + Sync + 'static> EventHandler for Framework<C> {
+    async fn ready(&self, ctx: Context, _ready: Ready) {
+        self.register_commands_once(&ctx).await;
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        self.handle_interaction(&ctx, &interaction).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        checks::{Check, CheckFailure},
+        testing::{ComponentInteractionBuilder, InteractionBuilder},
+        ApplicationCommandInteractionHandler, Command,
+    };
+    use serenity::{
+        builder::{
+            CreateApplicationCommand, CreateApplicationCommands, CreateButton, CreateSelectMenu,
+        },
+        model::channel::Message,
+    };
+    use slashies_test::FakeDiscord;
+
+    /// A command that can only be used in guilds
+    struct GuildOnly;
+
+    #[async_trait]
+    impl Check for GuildOnly {
+        async fn check(
+            _ctx: &Context,
+            command: &ApplicationCommandInteraction,
+        ) -> Result<(), CheckFailure> {
+            command
+                .guild_id
+                .map(|_| ())
+                .ok_or_else(|| CheckFailure::new("Only in guilds"))
+        }
+    }
+
+    struct PingCommand;
+
+    #[async_trait]
+    impl ApplicationCommandInteractionHandler for PingCommand {
+        async fn invoke(
+            &self,
+            ctx: &Context,
+            command: &ApplicationCommandInteraction,
+        ) -> Result<(), InvocationError> {
+            command
+                .create_interaction_response(&ctx.http, |response| {
+                    response
+                        .kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|data| data.content("pong"))
+                })
+                .await?;
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Command for PingCommand {
+        fn parse(_command: &ApplicationCommandInteraction) -> Result<Self, ParseError> {
+            Ok(Self)
+        }
+
+        fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+            command.name("ping").description("Ping the bot")
+        }
+
+        fn name() -> String {
+            "ping".to_owned()
+        }
+
+        async fn run_checks(
+            &self,
+            ctx: &Context,
+            command: &ApplicationCommandInteraction,
+        ) -> Result<(), InvocationError> {
+            GuildOnly::check(ctx, command).await?;
+            Ok(())
+        }
+    }
+
+    struct BotCommands(PingCommand);
+
+    #[async_trait]
+    impl Commands for BotCommands {
+        fn parse(
+            _ctx: &Context,
+            command: &ApplicationCommandInteraction,
+        ) -> Result<Self, ParseError> {
+            match command.data.name.as_str() {
+                "ping" => Ok(Self(PingCommand::parse(command)?)),
+                name => Err(ParseError::UnknownCommand {
+                    name: name.to_owned(),
+                }),
+            }
+        }
+
+        async fn invoke(
+            &self,
+            ctx: &Context,
+            command: &ApplicationCommandInteraction,
+        ) -> Result<(), InvocationError> {
+            self.0.run_checks(ctx, command).await?;
+            self.0.invoke(ctx, command).await
+        }
+
+        async fn autocomplete(
+            _ctx: &Context,
+            _interaction: &AutocompleteInteraction,
+        ) -> Result<(), InvocationError> {
+            Ok(())
+        }
+
+        fn register(commands: &mut CreateApplicationCommands) -> &mut CreateApplicationCommands {
+            commands.create_application_command(PingCommand::register)
+        }
+    }
+
+    #[tokio::test]
+    async fn commands_are_registered_and_invoked() {
+        let discord = FakeDiscord::start().await;
+        let ctx = discord.context();
+        let framework = Framework::<BotCommands>::new().register_in_guild(GuildId(1));
+
+        framework.register_commands(&ctx).await.unwrap();
+        assert_eq!(discord.commands(Some(GuildId(1)))[0]["name"], "ping");
+
+        let interaction = InteractionBuilder::new("ping").build();
+        framework
+            .handle_interaction(&ctx, &Interaction::ApplicationCommand(interaction))
+            .await;
+        assert_eq!(
+            discord.interaction_responses()[0]["data"]["content"],
+            "pong"
+        );
+    }
+
+    #[tokio::test]
+    async fn commands_are_only_registered_on_the_first_ready() {
+        let discord = FakeDiscord::start().await;
+        let ctx = discord.context();
+        let framework = Framework::<BotCommands>::new().register_globally();
+
+        // Every shard that connects receives a ready event
+        framework.register_commands_once(&ctx).await;
+        framework.register_commands_once(&ctx).await;
+        assert_eq!(
+            discord
+                .requests()
+                .iter()
+                .filter(|request| request.method == "PUT")
+                .count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_are_replied_to_ephemerally() {
+        let discord = FakeDiscord::start().await;
+        let ctx = discord.context();
+        let framework = Framework::<BotCommands>::new();
+
+        let interaction = InteractionBuilder::new("ping").direct_message().build();
+        framework
+            .handle_interaction(&ctx, &Interaction::ApplicationCommand(interaction))
+            .await;
+        let interaction = InteractionBuilder::new("unknown").build();
+        framework
+            .handle_interaction(&ctx, &Interaction::ApplicationCommand(interaction))
+            .await;
+
+        let responses = discord.interaction_responses();
+        assert_eq!(responses[0]["data"]["content"], "Only in guilds");
+        assert_eq!(responses[1]["data"]["content"], GENERIC_ERROR_MESSAGE);
+        // 64 is the ephemeral flag
+        assert!(responses
+            .iter()
+            .all(|response| response["data"]["flags"] == 64));
+    }
+
+    /// A page of results, stored in the custom ID of the buttons that show it
+    struct ResultsPage(u32);
+
+    impl CustomIdCodec for ResultsPage {
+        fn custom_id() -> String {
+            "results".to_owned()
+        }
+
+        fn version() -> u32 {
+            2
+        }
+
+        fn encode_fields(&self) -> Vec<String> {
+            vec![self.0.to_string()]
+        }
+
+        fn decode_fields(fields: &[&str]) -> Option<Self> {
+            match fields {
+                [page] => page.parse().ok().map(Self),
+                _ => None,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MessageComponentInteractionHandler for ResultsPage {
+        async fn invoke(
+            &mut self,
+            ctx: &Context,
+            interaction: &MessageComponentInteraction,
+            _original_message: &mut Message,
+        ) {
+            let content = format!("page {}", self.0);
+            interaction
+                .create_interaction_response(&ctx.http, |response| {
+                    response
+                        .kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|data| data.content(content))
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn components_are_routed_by_their_state() {
+        let discord = FakeDiscord::start().await;
+        let ctx = discord.context();
+        let router = ComponentRouter::new().route::<ResultsPage>();
+
+        let interaction = ComponentInteractionBuilder::new("results:2:3").build();
+        router.handle_component(&ctx, &interaction).await.unwrap();
+        assert_eq!(
+            discord.interaction_responses()[0]["data"]["content"],
+            "page 3"
+        );
+
+        for (custom_id, expected) in [
+            ("other:2:3", "unknown component `other:2:3`"),
+            (
+                "results:1:3",
+                "custom ID `results:1:3` is from an outdated version",
+            ),
+            ("results:2:three", "invalid custom ID `results:2:three`"),
+        ] {
+            let interaction = ComponentInteractionBuilder::new(custom_id).build();
+            let error = router
+                .handle_component(&ctx, &interaction)
+                .await
+                .unwrap_err();
+            assert_eq!(error.to_string(), expected);
+        }
+    }
+
+    /// Update the message that the component is attached to
+    async fn update_message(
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+        content: String,
+    ) -> Result<(), InvocationError> {
+        interaction
+            .create_interaction_response(&ctx.http, |response| {
+                response
+                    .kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|data| data.content(content))
+            })
+            .await?;
+        Ok(())
+    }
+
+    #[derive(Debug)]
+    enum Genre {
+        Horror,
+        Comedy,
+    }
+
+    impl SelectMenu for Genre {
+        fn custom_id() -> String {
+            "genres".to_owned()
+        }
+
+        fn parse_value(value: &str) -> Result<Self, ParseError> {
+            match value {
+                "Horror" => Ok(Self::Horror),
+                "Comedy" => Ok(Self::Comedy),
+                _ => Err(ParseError::InvalidCustomId {
+                    custom_id: Self::custom_id(),
+                }),
+            }
+        }
+
+        fn create(menu: &mut CreateSelectMenu) -> &mut CreateSelectMenu {
+            menu.custom_id(Self::custom_id())
+        }
+    }
+
+    struct Recommend;
+
+    #[async_trait]
+    impl SelectMenuHandler<Genre> for Recommend {
+        async fn handle_selection(
+            ctx: &Context,
+            interaction: &MessageComponentInteraction,
+            selected: Vec<Genre>,
+        ) -> Result<(), InvocationError> {
+            update_message(ctx, interaction, format!("{:?}", selected)).await
+        }
+    }
+
+    #[derive(Debug)]
+    enum Vote {
+        Yes(u64),
+        No(u64),
+    }
+
+    impl Buttons for Vote {
+        fn base_custom_id() -> String {
+            "vote".to_owned()
+        }
+
+        fn parse_custom_id(custom_id: &str) -> Result<Self, ParseError> {
+            let invalid = || ParseError::InvalidCustomId {
+                custom_id: custom_id.to_owned(),
+            };
+            let parts = custom_id.split(':').collect::<Vec<_>>();
+            match parts[..] {
+                ["vote", "Yes", poll] => Ok(Self::Yes(poll.parse().map_err(|_| invalid())?)),
+                ["vote", "No", poll] => Ok(Self::No(poll.parse().map_err(|_| invalid())?)),
+                _ => Err(invalid()),
+            }
+        }
+
+        fn custom_id(&self) -> String {
+            match self {
+                Self::Yes(poll) => format!("vote:Yes:{}", poll),
+                Self::No(poll) => format!("vote:No:{}", poll),
+            }
+        }
+
+        fn create<'a>(&self, button: &'a mut CreateButton) -> &'a mut CreateButton {
+            button.custom_id(self.custom_id())
+        }
+    }
+
+    struct Tally;
+
+    #[async_trait]
+    impl ButtonsHandler<Vote> for Tally {
+        async fn handle_click(
+            ctx: &Context,
+            interaction: &MessageComponentInteraction,
+            button: Vote,
+        ) -> Result<(), InvocationError> {
+            update_message(ctx, interaction, format!("{:?}", button)).await
+        }
+    }
+
+    #[tokio::test]
+    async fn components_are_routed_through_the_framework() {
+        let discord = FakeDiscord::start().await;
+        let ctx = discord.context();
+        let framework = Framework::<BotCommands>::new().on_component(
+            ComponentRouter::new()
+                .route::<ResultsPage>()
+                .select_menu::<Genre, Recommend>()
+                .buttons::<Vote, Tally>(),
+        );
+
+        for interaction in [
+            ComponentInteractionBuilder::new("genres").values(["Comedy", "Horror"]),
+            ComponentInteractionBuilder::new("vote:No:7"),
+            ComponentInteractionBuilder::new("results:2:4"),
+            ComponentInteractionBuilder::new("vote:Maybe:7"),
+        ] {
+            framework
+                .handle_interaction(&ctx, &Interaction::MessageComponent(interaction.build()))
+                .await;
+        }
+
+        let responses = discord.interaction_responses();
+        let contents = responses
+            .iter()
+            .map(|response| response["data"]["content"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            contents,
+            vec!["[Comedy, Horror]", "No(7)", "page 4", GENERIC_ERROR_MESSAGE]
+        );
+    }
+}
@@ -51,8 +51,8 @@
 //!                    .kind(InteractionResponseType::ChannelMessageWithSource)
 //!                    .interaction_response_data(|message| message.content(greeting))
 //!            })
-//!            .await
-//!            .map_err(|_| InvocationError)
+//!            .await?;
+//!        Ok(())
 //!    }
 //! }
 //!
@@ -105,40 +105,383 @@
 //!     }
 //! }
 //! ```
+//!
+//! Step 4 can also be replaced by [`framework::Framework`], an event handler that registers the
+//! commands, routes interactions to them and replies to errors rather than panicking.
 
 #![warn(missing_docs)]
 use serenity::{
     async_trait,
-    builder::{CreateApplicationCommand, CreateApplicationCommandOption},
+    builder::{
+        CreateApplicationCommand, CreateApplicationCommandOption, CreateApplicationCommands,
+        CreateButton, CreateComponents, CreateInteractionResponse, CreateSelectMenu,
+    },
     client::Context,
     model::{
         channel::Message,
         interactions::{
             application_command::{
                 ApplicationCommandInteraction, ApplicationCommandInteractionDataOption,
+                ApplicationCommandInteractionDataOptionValue, ApplicationCommandOptionType,
+                ApplicationCommandType,
             },
+            autocomplete::AutocompleteInteraction,
             message_component::MessageComponentInteraction,
+            modal::ModalSubmitInteraction,
         },
+        permissions::Permissions,
     },
 };
+use std::{error::Error, fmt};
 
 /// This module contains logic for parsing Discord types from interactions into rust types
 pub mod parsable;
 
+/// This module contains the [`Check`](checks::Check) trait for preconditions that must hold before
+/// a command is invoked
+pub mod checks;
+
+/// This module contains the cooldowns that limit how often a command can be used
+pub mod cooldown;
+
+/// This module contains a serenity event handler that registers and runs a set of commands
+pub mod framework;
+
+/// This module contains the [`Middleware`](middleware::Middleware) trait for hooks that run around
+/// every command, and a [`Dispatcher`](middleware::Dispatcher) that runs them
+pub mod middleware;
+
+/// This module contains a registry that routes message component interactions to a handler for
+/// each message
+pub mod sessions;
+
+/// This module contains logic for registering commands by only pushing the changes since they were
+/// last registered
+pub mod registration;
+
+/// This module contains logic for registering localized names and descriptions for commands
+pub mod localization;
+
+/// This module contains logic for exporting commands as the JSON that is sent to Discord to register
+/// them, without needing a connection to Discord
+pub mod manifest;
+
+/// This module contains helpers for building interactions offline so that commands can be tested
+/// without a connection to Discord
+///
+/// It is only available with the `testing` feature, which is meant to be enabled for
+/// dev-dependencies.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 /// An error that occured while trying to parse a command
+///
+/// Errors relating to a specific option carry the path to that option, starting with the name of
+/// the command, e.g. `["permissions", "user", "edit", "channel"]`.
+/// ```
+/// # use slashies::ParseError;
+/// # use serenity::model::interactions::application_command::ApplicationCommandOptionType;
+/// let error = ParseError::missing_option(ApplicationCommandOptionType::Channel)
+///     .in_option("channel")
+///     .in_option("edit")
+///     .in_option("user")
+///     .in_option("permissions");
+/// assert_eq!(
+///     error.to_string(),
+///     "missing option `permissions user edit channel` (expected Channel)"
+/// );
+/// ```
 #[derive(Debug, Clone)]
 pub enum ParseError {
     /// A required option was missing
-    MissingOption,
+    MissingOption {
+        /// The path to the missing option
+        path: Vec<String>,
+        /// The type of option we expected to find
+        expected: ApplicationCommandOptionType,
+    },
     /// An option was malformed
-    InvalidOption,
+    InvalidOption {
+        /// The path to the malformed option
+        path: Vec<String>,
+        /// The type of option we expected to find
+        expected: ApplicationCommandOptionType,
+        /// The type of value we actually received
+        received: ApplicationCommandOptionType,
+    },
+    /// A number was outside of the range allowed for the option
+    OutOfRange {
+        /// The path to the option
+        path: Vec<String>,
+        /// The value we received
+        value: f64,
+        /// The minimum allowed value
+        min: f64,
+        /// The maximum allowed value
+        max: f64,
+    },
+    /// The target of a user or message command was missing or of the wrong type
+    InvalidTarget {
+        /// The type of command we expected the target for
+        expected: ApplicationCommandType,
+    },
     /// The command was not one we know about
-    UnknownCommand,
+    UnknownCommand {
+        /// The name of the command
+        name: String,
+    },
+    /// A text input was missing from a submitted modal
+    MissingInput {
+        /// The custom ID of the missing text input
+        custom_id: String,
+    },
+    /// The modal or message component was not one we know about
+    UnknownComponent {
+        /// The custom ID of the modal or component
+        custom_id: String,
+    },
+    /// The values stored in the custom ID of a message component were malformed
+    InvalidCustomId {
+        /// The custom ID of the component
+        custom_id: String,
+    },
+    /// The state stored in the custom ID of a message component was encoded by a different version
+    /// of the bot (see [`CustomIdCodec`])
+    OutdatedCustomId {
+        /// The custom ID of the component
+        custom_id: String,
+    },
+    /// A select menu option was selected that isn't one of the menu's options
+    InvalidSelection {
+        /// The custom ID of the select menu
+        custom_id: String,
+        /// The value of the selected option
+        value: String,
+    },
 }
 
+impl ParseError {
+    /// Create a [`ParseError::MissingOption`] for an option of the given type
+    pub fn missing_option(expected: ApplicationCommandOptionType) -> Self {
+        Self::MissingOption {
+            path: Vec::new(),
+            expected,
+        }
+    }
+
+    /// Create a [`ParseError::OutOfRange`] for an option that received a value outside of the
+    /// given range
+    pub fn out_of_range(value: f64, min: f64, max: f64) -> Self {
+        Self::OutOfRange {
+            path: Vec::new(),
+            value,
+            min,
+            max,
+        }
+    }
+
+    /// Create a [`ParseError::InvalidOption`] for an option of the given type that received the
+    /// given value
+    pub fn invalid_option(
+        expected: ApplicationCommandOptionType,
+        received: &ApplicationCommandInteractionDataOptionValue,
+    ) -> Self {
+        let received = match received {
+            ApplicationCommandInteractionDataOptionValue::String(_) => {
+                ApplicationCommandOptionType::String
+            }
+            ApplicationCommandInteractionDataOptionValue::Integer(_) => {
+                ApplicationCommandOptionType::Integer
+            }
+            ApplicationCommandInteractionDataOptionValue::Boolean(_) => {
+                ApplicationCommandOptionType::Boolean
+            }
+            ApplicationCommandInteractionDataOptionValue::User(_, _) => {
+                ApplicationCommandOptionType::User
+            }
+            ApplicationCommandInteractionDataOptionValue::Channel(_) => {
+                ApplicationCommandOptionType::Channel
+            }
+            ApplicationCommandInteractionDataOptionValue::Role(_) => {
+                ApplicationCommandOptionType::Role
+            }
+            ApplicationCommandInteractionDataOptionValue::Number(_) => {
+                ApplicationCommandOptionType::Number
+            }
+            ApplicationCommandInteractionDataOptionValue::Attachment(_) => {
+                ApplicationCommandOptionType::Attachment
+            }
+            _ => ApplicationCommandOptionType::Unknown,
+        };
+        Self::InvalidOption {
+            path: Vec::new(),
+            expected,
+            received,
+        }
+    }
+
+    /// Add the name of the option, subcommand or command that this error occured within to the
+    /// start of the error's path. This is called by the derive macros as the error is passed back
+    /// up through the command.
+    pub fn in_option(mut self, name: impl Into<String>) -> Self {
+        if let Self::MissingOption { ref mut path, .. }
+        | Self::InvalidOption { ref mut path, .. }
+        | Self::OutOfRange { ref mut path, .. } = self
+        {
+            path.insert(0, name.into());
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingOption { path, expected } => write!(
+                f,
+                "missing option `{}` (expected {:?})",
+                path.join(" "),
+                expected
+            ),
+            Self::InvalidOption {
+                path,
+                expected,
+                received,
+            } => write!(
+                f,
+                "invalid option `{}` (expected {:?}, received {:?})",
+                path.join(" "),
+                expected,
+                received
+            ),
+            Self::OutOfRange {
+                path,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "option `{}` must be between {} and {} (received {})",
+                path.join(" "),
+                min,
+                max,
+                value
+            ),
+            Self::InvalidTarget { expected } => {
+                write!(f, "invalid target for {:?} command", expected)
+            }
+            Self::UnknownCommand { name } => write!(f, "unknown command `{}`", name),
+            Self::MissingInput { custom_id } => write!(f, "missing input `{}`", custom_id),
+            Self::UnknownComponent { custom_id } => {
+                write!(f, "unknown component `{}`", custom_id)
+            }
+            Self::InvalidCustomId { custom_id } => write!(f, "invalid custom ID `{}`", custom_id),
+            Self::OutdatedCustomId { custom_id } => {
+                write!(f, "custom ID `{}` is from an outdated version", custom_id)
+            }
+            Self::InvalidSelection { custom_id, value } => {
+                write!(f, "invalid selection `{}` in `{}`", value, custom_id)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
 /// An error that occured while trying to invoke a command
-#[derive(Debug, Clone)]
-pub struct InvocationError;
+///
+/// The error wraps the underlying cause, and can optionally hold a message to show to the user who
+/// invoked the command. Errors from serenity and [`ParseError`]s can be converted automatically, so
+/// you can use `?` within your handlers:
+/// ```
+/// # use slashies::*;
+/// # use serenity::prelude::*;
+/// # use serenity::model::interactions::InteractionResponseType;
+/// # use serenity::model::prelude::application_command::*;
+/// async fn respond(
+///     ctx: &Context,
+///     command: &ApplicationCommandInteraction,
+/// ) -> Result<(), InvocationError> {
+///     command
+///         .create_interaction_response(&ctx.http, |response| {
+///             response
+///                 .kind(InteractionResponseType::ChannelMessageWithSource)
+///                 .interaction_response_data(|message| message.content("Hello!"))
+///         })
+///         .await?;
+///     Ok(())
+/// }
+///
+/// let error = InvocationError::new("database unavailable")
+///     .with_user_message("Sorry, something went wrong - please try again later");
+/// assert_eq!(error.to_string(), "database unavailable");
+/// assert_eq!(
+///     error.user_message(),
+///     Some("Sorry, something went wrong - please try again later")
+/// );
+/// ```
+#[derive(Debug)]
+pub struct InvocationError {
+    source: Box<dyn Error + Send + Sync>,
+    user_message: Option<String>,
+}
+
+impl InvocationError {
+    /// Create an error from its underlying cause. Anything that can be converted into a boxed
+    /// error can be used, including strings.
+    pub fn new(source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self {
+            source: source.into(),
+            user_message: None,
+        }
+    }
+
+    /// Add a message that should be shown to the user who invoked the command
+    pub fn with_user_message(mut self, message: impl Into<String>) -> Self {
+        self.user_message = Some(message.into());
+        self
+    }
+
+    /// The message that should be shown to the user who invoked the command, if there is one
+    pub fn user_message(&self) -> Option<&str> {
+        self.user_message.as_deref()
+    }
+
+    /// The failed check that stopped the command from being invoked, if that is what caused this
+    /// error
+    pub fn check_failure(&self) -> Option<&checks::CheckFailure> {
+        self.source.downcast_ref()
+    }
+
+    /// The cooldown that stopped the command from being invoked, if that is what caused this error
+    pub fn cooldown(&self) -> Option<&cooldown::CooldownError> {
+        self.source.downcast_ref()
+    }
+}
+
+impl fmt::Display for InvocationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl Error for InvocationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+impl From<serenity::Error> for InvocationError {
+    fn from(error: serenity::Error) -> Self {
+        Self::new(error)
+    }
+}
+
+impl From<ParseError> for InvocationError {
+    fn from(error: ParseError) -> Self {
+        Self::new(error)
+    }
+}
 
 /// This trait provides the methods needed to parse and register a slash command.
 ///
@@ -170,9 +513,18 @@ pub struct InvocationError;
 /// ```
 /// To derive the trait, you must provide the following (see the example above):
 /// - Docstrings for the struct and all fields (these will be used for the
-/// descriptions of the command and its options)
+///   descriptions of the command and its options)
 /// - The name of the command via the `name` attribute
 ///
+/// The names of the command, its subcommands and its options (the field names) are checked against
+/// Discord's naming rules at compile time: they must be 1-32 characters long, lowercase, and only
+/// contain letters, numbers, `-` and `_`.
+///
+/// Discord's other limits on commands are also checked at compile time: descriptions must be 1-100
+/// characters long, there can be at most 25 options, choices or subcommands in one place, choice
+/// names and values can be at most 100 characters long, and all the names, descriptions and
+/// choices of a command must add up to at most 4000 characters (see [`Command::CHARACTER_COUNT`]).
+///
 /// All fields must implement the [`parsable::ParsableCommandOption`] trait - see the docs for the
 /// trait for a list of types supported out of the box.
 ///
@@ -182,11 +534,99 @@ pub struct InvocationError;
 /// | Attribute     | Explanation                                                                                                         | Examples                                                 | Applicable Discord types |
 /// |---------------|---------------------------------------------------------------------------------------------------------------------|----------------------------------------------------------|--------------------------|
 /// | choice        | Limits the user's input to specific choices - use the attribute on the field multiple times, once for each choice.  | `#[choice("Action")]` `#[choice("First", 1)]`            | STRING, INTEGER, NUMBER  |
-/// | min           | Limits the user's input to be at least this value.                                                                  | `#[min = 0.0]`                                           | INTEGER, NUMBER          |
-/// | max           | Limits the user's input to be at most this value.                                                                   | `#[max = 10.0]`                                          | INTEGER, NUMBER          |
+/// | min           | Limits the user's input to be at least this value. Not allowed on bounded types like [`parsable::BoundedInt`].      | `#[min = 0.0]`                                           | INTEGER, NUMBER          |
+/// | max           | Limits the user's input to be at most this value. Not allowed on bounded types like [`parsable::BoundedInt`].       | `#[max = 10.0]`                                          | INTEGER, NUMBER          |
 /// | channel_types | Limits the user's choice of channels to specific types of channels                                                  | `#[channel_types(ChannelType::Text, ChannelType::News)]` | CHANNEL                  |
+/// | autocomplete  | Suggests values as the user types via an async handler, instead of choices - see [`AutocompleteHandler`]            | `#[autocomplete = "suggest_genres"]`                     | STRING, INTEGER, NUMBER  |
+///
+/// Who can use the command, and how often, can be restricted via these attributes:
+///
+/// | Attribute                  | Explanation                                                                                                 | Examples                                                      |
+/// |----------------------------|-------------------------------------------------------------------------------------------------------------|---------------------------------------------------------------|
+/// | default_member_permissions | Only members with all of these [`Permissions`] can use the command by default (empty means only admins)     | `#[default_member_permissions(MANAGE_ROLES \| KICK_MEMBERS)]` |
+/// | dm_permission              | Whether the command can be used in DMs with the bot (it can by default)                                     | `#[dm_permission = false]`                                    |
+/// | check                      | Only invokes the command if these [checks](checks::Check) pass - can also be used on subcommands and groups | `#[check(InGuild)]` `#[check(InGuild, IsModerator)]`          |
+/// | cooldown                   | Limits how often the command can be used (see [`cooldown::Cooldown`]) - can also be used on subcommands     | `#[cooldown(per = "user", rate = 3, window_secs = 60)]`       |
+///
+/// ```
+/// # use slashies::*;
+/// # use slashies::parsable::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// use serenity::model::permissions::Permissions;
+///
+/// /// Kick a user
+/// #[derive(Debug, Command)]
+/// #[name = "kick"]
+/// #[default_member_permissions(KICK_MEMBERS)]
+/// #[dm_permission = false]
+/// struct KickCommand {
+///     /// The user to kick
+///     user: UserInput,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for KickCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #        unimplemented!()
+/// #    }
+/// # }
+/// assert_eq!(KickCommand::default_member_permissions(), Some(Permissions::KICK_MEMBERS));
+/// let command = manifest::command::<KickCommand>();
+/// assert_eq!(command["default_member_permissions"], Permissions::KICK_MEMBERS.bits().to_string());
+/// assert_eq!(command["dm_permission"], false);
+/// ```
+///
+/// Names and descriptions can be translated for Discord's other locales (see
+/// [`localization::LOCALES`]) via the `name_localized` and `description_localized` attributes on
+/// the command, its options and its subcommands. Choices take their translations inside the
+/// `choice` attribute. Translated names and descriptions are checked against the same rules as the
+/// originals:
+/// ```
+/// # use slashies::*;
+/// # use slashies::parsable::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// /// Greet a user
+/// #[derive(Debug, Command)]
+/// #[name = "greet"]
+/// #[name_localized("de", "grüßen")]
+/// #[description_localized("de", "Einen Benutzer grüßen")]
+/// #[description_localized("es-419", "Saludar a un usuario")]
+/// struct HelloCommand {
+///     /// How to greet the user
+///     #[name_localized("de", "art")]
+///     #[description_localized("de", "Wie der Benutzer gegrüßt werden soll")]
+///     #[choice("Formally", "formal", name_localized("de", "Förmlich"))]
+///     #[choice("Casually", "casual", name_localized("de", "Locker"))]
+///     style: String,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HelloCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #        unimplemented!()
+/// #    }
+/// # }
+/// let command = manifest::command::<HelloCommand>();
+/// assert_eq!(command["name_localizations"]["de"], "grüßen");
+/// assert_eq!(command["description_localizations"]["es-419"], "Saludar a un usuario");
+/// assert_eq!(command["options"][0]["name_localizations"]["de"], "art");
+/// assert_eq!(command["options"][0]["choices"][0]["name_localizations"]["de"], "Förmlich");
+/// ```
 ///
 /// For how to work with subcommands, see the documentation for the [`SubCommand`] trait
+#[async_trait]
 pub trait Command: ApplicationCommandInteractionHandler + Sized {
     /// Try to parse the interaction as this type of command
     fn parse(command: &ApplicationCommandInteraction) -> Result<Self, ParseError>;
@@ -194,6 +634,77 @@ pub trait Command: ApplicationCommandInteractionHandler + Sized {
     fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand;
     /// The name of the command
     fn name() -> String;
+    /// The type of the command - names only need to be unique among commands of the same type
+    ///
+    /// This is [`ApplicationCommandType::ChatInput`] for slash commands. The derive macros for user
+    /// and message commands override it.
+    fn kind() -> ApplicationCommandType {
+        ApplicationCommandType::ChatInput
+    }
+    /// An estimate of how many characters this command uses of Discord's limit of 4000 characters
+    /// for the combined names, descriptions and choice values of a command
+    ///
+    /// The derive macro calculates this at compile time and refuses to compile commands that go
+    /// over the limit. Hand written implementations that do not provide it count as 0.
+    const CHARACTER_COUNT: usize = 0;
+    /// The permissions a member needs to use this command by default, or `None` if anyone can use
+    /// it. Server admins can still change who can use the command.
+    fn default_member_permissions() -> Option<Permissions> {
+        None
+    }
+    /// Whether this command can be used in DMs, or `None` to use Discord's default (allowed)
+    fn dm_permission() -> Option<bool> {
+        None
+    }
+    /// Add the permissions given by [`Command::default_member_permissions`] and
+    /// [`Command::dm_permission`] to the command being registered
+    ///
+    /// The derive macro calls this at the end of [`Command::register`], so hand written
+    /// implementations should do the same if they provide permissions.
+    fn register_permissions(
+        command: &mut CreateApplicationCommand,
+    ) -> &mut CreateApplicationCommand {
+        if let Some(permissions) = Self::default_member_permissions() {
+            command.default_member_permissions(permissions);
+        }
+        if let Some(dm_permission) = Self::dm_permission() {
+            command.dm_permission(dm_permission);
+        }
+        command
+    }
+    /// Evaluate the [checks](checks::Check) for this command (and any subcommand or subcommand group
+    /// it is invoking), returning an error for the first one that fails
+    ///
+    /// [`Commands::invoke`] calls this before invoking the command.
+    async fn run_checks(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        Ok(())
+    }
+    /// The [cooldowns](cooldown::Cooldown) that apply to this invocation of the command: those
+    /// declared on the command itself, and on the subcommand group and subcommand being invoked
+    ///
+    /// Once the checks have passed, [`Commands::invoke`] [enforces](cooldown::enforce) them all
+    /// together, so a use only counts against their buckets if none of them are full.
+    fn cooldowns(&self) -> Vec<cooldown::DeclaredCooldown> {
+        Vec::new()
+    }
+    /// Respond to an autocomplete interaction for one of this command's options, given the options
+    /// provided at this level of the command
+    ///
+    /// The derive macro forwards this to the [`AutocompleteHandler`] it implements. Hand written
+    /// implementations only need to provide it if its options use autocomplete.
+    async fn route_autocomplete(
+        _ctx: &Context,
+        _interaction: &AutocompleteInteraction,
+        _options: &[ApplicationCommandInteractionDataOption],
+    ) -> Result<(), InvocationError> {
+        Err(InvocationError::new(
+            "no autocomplete handler for the focused option",
+        ))
+    }
 }
 
 /// This trait provides the functions necessary to parse and register a subcommand for a slash
@@ -263,6 +774,7 @@ pub trait Command: ApplicationCommandInteractionHandler + Sized {
 /// [`SubCommand`].
 ///
 /// To organize subcommands into groups, see the [`SubCommandGroup`] trait
+#[async_trait]
 pub trait SubCommand: Sized {
     /// Try to parse this from a command option
     fn parse(option: Option<&ApplicationCommandInteractionDataOption>) -> Result<Self, ParseError>;
@@ -270,6 +782,23 @@ pub trait SubCommand: Sized {
     fn register_sub_options(
         option: &mut CreateApplicationCommandOption,
     ) -> &mut CreateApplicationCommandOption;
+    /// An estimate of how many characters the options of this subcommand use of the 4000
+    /// character limit of the command it belongs to (see [`Command::CHARACTER_COUNT`])
+    const CHARACTER_COUNT: usize = 0;
+    /// Respond to an autocomplete interaction for one of this subcommand's options, given the options
+    /// provided at this level of the command
+    ///
+    /// The derive macro forwards this to the [`AutocompleteHandler`] it implements. Hand written
+    /// implementations only need to provide it if its options use autocomplete.
+    async fn route_autocomplete(
+        _ctx: &Context,
+        _interaction: &AutocompleteInteraction,
+        _options: &[ApplicationCommandInteractionDataOption],
+    ) -> Result<(), InvocationError> {
+        Err(InvocationError::new(
+            "no autocomplete handler for the focused option",
+        ))
+    }
 }
 
 /// This trait provides the functions necessary to parse and register a subcommand group for a slash
@@ -356,6 +885,10 @@ pub trait SubCommand: Sized {
 /// }
 /// ```
 /// Note that you can mix subcommands and subcommand groups in a command as in the example above.
+///
+/// [Cooldowns](cooldown::Cooldown) for a whole subcommand group are declared with the `cooldown`
+/// attribute on the enum deriving [`SubCommandGroup`], rather than on its variant in the command.
+#[async_trait]
 pub trait SubCommandGroup: Sized {
     /// Try to parse this from a command option
     fn parse(option: Option<&ApplicationCommandInteractionDataOption>) -> Result<Self, ParseError>;
@@ -363,6 +896,635 @@ pub trait SubCommandGroup: Sized {
     fn register_sub_options(
         option: &mut CreateApplicationCommandOption,
     ) -> &mut CreateApplicationCommandOption;
+    /// An estimate of how many characters the subcommands of this group use of the 4000 character
+    /// limit of the command it belongs to (see [`Command::CHARACTER_COUNT`])
+    const CHARACTER_COUNT: usize = 0;
+    /// Evaluate the [checks](checks::Check) for the subcommand being invoked, returning an error for
+    /// the first one that fails
+    async fn run_checks(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        Ok(())
+    }
+    /// The [cooldowns](cooldown::Cooldown) declared on the subcommand group and on the subcommand
+    /// being invoked (see [`Command::cooldowns`]), given the path of the group (e.g. `"movies list"`)
+    fn cooldowns(&self, _path: &str) -> Vec<cooldown::DeclaredCooldown> {
+        Vec::new()
+    }
+    /// Respond to an autocomplete interaction for one of the options of its subcommands, given the options
+    /// provided at this level of the command
+    ///
+    /// The derive macro forwards this to the [`AutocompleteHandler`] it implements. Hand written
+    /// implementations only need to provide it if the options of its subcommands use autocomplete.
+    async fn route_autocomplete(
+        _ctx: &Context,
+        _interaction: &AutocompleteInteraction,
+        _options: &[ApplicationCommandInteractionDataOption],
+    ) -> Result<(), InvocationError> {
+        Err(InvocationError::new(
+            "no autocomplete handler for the focused option",
+        ))
+    }
+}
+
+/// This trait provides the methods needed to parse and register a user command - a command that
+/// appears under "Apps" when right clicking on a user.
+///
+/// For most use cases, just derive it via the macros crate:
+/// ```
+/// # use slashies::*;
+/// # use slashies::parsable::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// #[derive(Debug, UserCommand)]
+/// #[name = "High Five"]
+/// struct HighFiveCommand {
+///     user: UserInput,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HighFiveCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #        unimplemented!()
+/// #    }
+/// # }
+/// ```
+/// To derive the trait, the struct must have the name of the command via the `name` attribute and a
+/// single field for the target of the command, which must implement
+/// [`parsable::ParsableTarget`]. Unlike slash commands, user commands have no description or
+/// options, and their names may contain capital letters and spaces. The name can be translated
+/// via the `name_localized` attribute (e.g. `#[name_localized("de", "Abklatschen")]`), and who can use
+/// it by default is set with the `default_member_permissions` and `dm_permission` attributes, as for
+/// slash commands (see [`Command`]).
+///
+/// Deriving this trait also implements [`Command`] for the struct so that it can be registered via
+/// [`register_commands!`] and added to a [`Commands`] enum like any other command. If you implement
+/// this trait by hand, you can implement [`Command`] by delegating to it (including
+/// [`Command::kind`]).
+///
+/// Discord only requires names to be unique among commands of the same type, so a user command
+/// can share its name with a slash command in the same [`Commands`] enum:
+/// ```
+/// # use slashies::*;
+/// # use slashies::parsable::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::id::UserId;
+/// # use serenity::model::prelude::application_command::*;
+/// #[derive(Debug, UserCommand)]
+/// #[name = "highfive"]
+/// struct HighFiveUserCommand {
+///     user: UserInput,
+/// }
+///
+/// /// High five a user
+/// #[derive(Debug, Command)]
+/// #[name = "highfive"]
+/// struct HighFiveCommand {
+///     /// The user to high five
+///     user: UserInput,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HighFiveUserCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #        unimplemented!()
+/// #    }
+/// # }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HighFiveCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #        unimplemented!()
+/// #    }
+/// # }
+///
+/// #[derive(Debug, Commands)]
+/// enum BotCommands {
+///     HighFiveUser(HighFiveUserCommand),
+///     HighFive(HighFiveCommand),
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// let slash_command = InteractionBuilder::new("highfive")
+///     .option(OptionBuilder::user("user", UserId(2)))
+///     .resolved_user(UserId(2), "ferris")
+///     .build();
+/// assert!(matches!(
+///     BotCommands::parse(&ctx, &slash_command),
+///     Ok(BotCommands::HighFive(_))
+/// ));
+///
+/// let user_command = InteractionBuilder::new("highfive")
+///     .target_user(UserId(2))
+///     .resolved_user(UserId(2), "ferris")
+///     .build();
+/// assert!(matches!(
+///     BotCommands::parse(&ctx, &user_command),
+///     Ok(BotCommands::HighFiveUser(_))
+/// ));
+/// # }
+/// ```
+pub trait UserCommand: ApplicationCommandInteractionHandler + Sized {
+    /// Try to parse the interaction as this type of command
+    fn parse(command: &ApplicationCommandInteraction) -> Result<Self, ParseError>;
+    /// The name of the command
+    fn name() -> String;
+    /// Register this command so that it can be used
+    fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+        command
+            .name(Self::name())
+            .kind(ApplicationCommandType::User)
+    }
+}
+
+/// This trait provides the methods needed to parse and register a message command - a command that
+/// appears under "Apps" when right clicking on a message.
+///
+/// For most use cases, just derive it via the macros crate:
+/// ```
+/// # use slashies::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::channel::Message;
+/// # use serenity::model::prelude::application_command::*;
+/// #[derive(Debug, MessageCommand)]
+/// #[name = "Report message"]
+/// #[dm_permission = false]
+/// struct ReportCommand {
+///     message: Message,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for ReportCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #        unimplemented!()
+/// #    }
+/// # }
+/// let command = manifest::command::<ReportCommand>();
+/// assert_eq!(command["type"], 3);
+/// assert_eq!(command["dm_permission"], false);
+/// ```
+/// The requirements for deriving the trait are the same as for [`UserCommand`], except that the
+/// target field will usually be a [`Message`].
+///
+/// As with [`UserCommand`], deriving this trait also implements [`Command`] for the struct, so it
+/// can be mixed into a [`Commands`] enum alongside slash commands and user commands.
+pub trait MessageCommand: ApplicationCommandInteractionHandler + Sized {
+    /// Try to parse the interaction as this type of command
+    fn parse(command: &ApplicationCommandInteraction) -> Result<Self, ParseError>;
+    /// The name of the command
+    fn name() -> String;
+    /// Register this command so that it can be used
+    fn register(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
+        command
+            .name(Self::name())
+            .kind(ApplicationCommandType::Message)
+    }
+}
+
+/// This trait provides the methods needed to show a modal (a pop-up form of text inputs) and parse
+/// what the user submitted.
+///
+/// For most use cases, just derive it via the macros crate:
+/// ```
+/// # use slashies::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// /// Send feedback
+/// #[derive(Debug, Modal)]
+/// #[custom_id = "feedback"]
+/// struct FeedbackModal {
+///     /// Subject
+///     #[max_length = 80]
+///     subject: String,
+///
+///     /// What would you like to tell us?
+///     #[style(paragraph)]
+///     #[min_length = 10]
+///     #[placeholder = "The more detail the better"]
+///     details: String,
+///
+///     /// Can we contact you about this?
+///     contact: Option<String>,
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// # let command = InteractionBuilder::new("feedback").build();
+/// // Show the modal in response to a command or message component
+/// command
+///     .create_interaction_response(&ctx.http, FeedbackModal::create)
+///     .await
+///     .unwrap();
+/// # let response = &discord.interaction_responses()[0]["data"];
+/// # assert_eq!(response["custom_id"], "feedback");
+/// # assert_eq!(response["title"], "Send feedback");
+/// # assert_eq!(response["components"][1]["components"][0]["label"], "What would you like to tell us?");
+/// # assert_eq!(response["components"][2]["components"][0]["required"], false);
+///
+/// // Parse what the user submitted
+/// let submission = ModalSubmitBuilder::new("feedback")
+///     .input("subject", "Dark mode")
+///     .input("details", "Please add a dark mode")
+///     .input("contact", "")
+///     .build();
+/// let feedback = FeedbackModal::parse(&submission).unwrap();
+/// assert_eq!(feedback.subject, "Dark mode");
+/// assert_eq!(feedback.contact, None);
+/// # }
+/// ```
+/// To derive the trait, you must provide the following (see the example above):
+/// - A docstring for the struct, which will be used for the title of the modal
+/// - Docstrings for all fields, which will be used for the labels of the text inputs
+///
+/// The modal's custom ID is the name of the struct unless it is given via the `custom_id`
+/// attribute. The text inputs use the field names as their custom IDs.
+///
+/// Discord's limits on modals are checked at compile time: a modal must have 1-5 text inputs,
+/// and titles and labels must be 1-45 characters long.
+///
+/// All fields must implement the [`parsable::ParsableTextInput`] trait - use [`String`] for text
+/// inputs the user must fill in, or [`Option<String>`] for ones they can leave empty. You may also
+/// provide additional attributes for the text inputs:
+///
+/// | Attribute   | Explanation                                                                                      | Examples                                |
+/// |-------------|--------------------------------------------------------------------------------------------------|-----------------------------------------|
+/// | style       | Whether the input is a single line (the default) or a multi-line paragraph                       | `#[style(short)]` `#[style(paragraph)]` |
+/// | min_length  | The user's input must be at least this many characters long (0-4000)                             | `#[min_length = 10]`                    |
+/// | max_length  | The user's input can be at most this many characters long (1-4000)                               | `#[max_length = 200]`                   |
+/// | placeholder | Text shown in the input while it is empty (at most 100 characters)                               | `#[placeholder = "Tell us more"]`       |
+/// | required    | Overrides whether the user must fill in the input (by default, only `Option`s can be left empty) | `#[required]` `#[required = false]`     |
+pub trait Modal: Sized {
+    /// The custom ID identifying this modal when it is submitted
+    fn custom_id() -> String;
+    /// Try to parse the values the user submitted
+    fn parse(interaction: &ModalSubmitInteraction) -> Result<Self, ParseError>;
+    /// Build the response that shows this modal to the user
+    fn create<'a, 'b>(
+        response: &'a mut CreateInteractionResponse<'b>,
+    ) -> &'a mut CreateInteractionResponse<'b>;
+}
+
+/// Discord's limit on the number of buttons in an action row
+const MAX_BUTTONS_PER_ROW: usize = 5;
+
+/// This trait provides the methods needed to create a set of buttons and parse which one was
+/// clicked.
+///
+/// For most use cases, just derive it via the macros crate on an enum with a variant for each
+/// button. Variants can carry fields, which are stored in the button's custom ID and handed back
+/// when the button is clicked:
+/// ```
+/// # use slashies::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::prelude::*;
+/// # use serenity::model::interactions::InteractionResponseType;
+/// #[derive(Debug, PartialEq, Buttons)]
+/// #[custom_id = "poll"]
+/// enum PollButtons {
+///     /// Yes
+///     #[style(success)]
+///     #[emoji = "👍"]
+///     Yes { poll: u64 },
+///
+///     /// No
+///     #[style(danger)]
+///     #[emoji = "👎"]
+///     No { poll: u64 },
+///
+///     /// Show results
+///     #[style(secondary)]
+///     Results(u64, String),
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// # let command = InteractionBuilder::new("poll").build();
+/// let buttons = [
+///     PollButtons::Yes { poll: 7 },
+///     PollButtons::No { poll: 7 },
+///     PollButtons::Results(7, "votes".to_owned()),
+/// ];
+/// command
+///     .create_interaction_response(&ctx.http, |response| {
+///         response
+///             .kind(InteractionResponseType::ChannelMessageWithSource)
+///             .interaction_response_data(|message| {
+///                 message
+///                     .content("Should we get pizza?")
+///                     .components(|components| PollButtons::create_action_rows(&buttons, components))
+///             })
+///     })
+///     .await
+///     .unwrap();
+/// # let row = &discord.interaction_responses()[0]["data"]["components"][0];
+/// # assert_eq!(row["components"][0]["label"], "Yes");
+/// # assert_eq!(row["components"][0]["style"], 3);
+/// # assert_eq!(row["components"][0]["emoji"]["name"], "👍");
+/// assert_eq!(buttons[0].custom_id(), "poll:Yes:7");
+/// assert_eq!(buttons[2].custom_id(), "poll:Results:7:votes");
+/// // Fields without a maximum length can make the custom ID too long for Discord
+/// assert!(PollButtons::Results(7, "votes".repeat(20)).encode().is_err());
+/// # let mut components = serenity::builder::CreateComponents::default();
+/// # let many = (0..6).map(|poll| PollButtons::Yes { poll }).collect::<Vec<_>>();
+/// # PollButtons::create_action_rows(&many, &mut components);
+/// # assert_eq!(components.0.len(), 2);
+/// # assert_eq!(components.0[1]["components"].as_array().unwrap().len(), 1);
+///
+/// // When a button is clicked, parse the interaction back into the enum
+/// let interaction = ComponentInteractionBuilder::new("poll:No:7").build();
+/// assert_eq!(PollButtons::parse(&interaction).unwrap(), PollButtons::No { poll: 7 });
+/// # }
+/// ```
+/// To derive the trait, you must provide a docstring for each variant, which will be used for the
+/// label of the button (at most 80 characters). There can be at most 25 variants, as a message can
+/// only have 5 rows of 5 buttons. All fields must implement the [`parsable::CustomIdField`] trait.
+///
+/// The custom ID of each button is made up of the custom ID of the enum (the name of the enum,
+/// unless it is given via the `custom_id` attribute), the name of the variant and the values of its
+/// fields, separated by `:`. Discord only allows custom IDs of up to 100 characters. If every field
+/// of a variant has a maximum length (see [`parsable::CustomIdField::MAX_LENGTH`]), it is checked at
+/// compile time that the variant's custom ID will always fit. Otherwise, [`Buttons::encode`]
+/// returns an error when it doesn't.
+///
+/// You may also provide additional attributes for each button:
+///
+/// | Attribute | Explanation                                                                               | Examples                                     |
+/// |-----------|-------------------------------------------------------------------------------------------|----------------------------------------------|
+/// | style     | The colour of the button: `primary` (the default), `secondary`, `success` or `danger`     | `#[style(danger)]`                           |
+/// | emoji     | An emoji shown next to the label - either a unicode emoji or a custom one                 | `#[emoji = "👍"]` `#[emoji = "<:ferris:1>"]` |
+///
+/// Button clicks can be handled by routing them to a [`framework::ButtonsHandler`] with
+/// [`framework::ComponentRouter::buttons`].
+pub trait Buttons: Sized {
+    /// The custom ID of the enum, which the custom ID of each button starts with
+    fn base_custom_id() -> String;
+    /// Try to parse the custom ID of a button as one of these buttons
+    fn parse_custom_id(custom_id: &str) -> Result<Self, ParseError>;
+    /// The custom ID of this button, including the values of its fields
+    fn custom_id(&self) -> String;
+    /// The custom ID of this button, or an error if it is too long for Discord
+    fn encode(&self) -> Result<String, parsable::CustomIdTooLong> {
+        parsable::check_custom_id_length(self.custom_id())
+    }
+    /// Fill in the label, style, emoji and custom ID of this button
+    fn create<'a>(&self, button: &'a mut CreateButton) -> &'a mut CreateButton;
+    /// Try to parse the button that was clicked in an interaction
+    fn parse(interaction: &MessageComponentInteraction) -> Result<Self, ParseError> {
+        Self::parse_custom_id(&interaction.data.custom_id)
+    }
+    /// Add action rows containing the given buttons, starting a new row after every 5 buttons (the
+    /// most Discord allows in a row)
+    fn create_action_rows<'a>(
+        buttons: &[Self],
+        components: &'a mut CreateComponents,
+    ) -> &'a mut CreateComponents {
+        for row_buttons in buttons.chunks(MAX_BUTTONS_PER_ROW) {
+            components.create_action_row(|row| {
+                for button in row_buttons {
+                    row.create_button(|create| button.create(create));
+                }
+                row
+            });
+        }
+        components
+    }
+}
+
+/// This trait provides the methods needed to store state in the custom ID of a message component
+/// and read it back when the component is used.
+///
+/// For most use cases, just derive it via the macros crate on a struct:
+/// ```
+/// # use slashies::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// #[derive(Debug, PartialEq, CustomIdCodec)]
+/// #[custom_id = "results"]
+/// #[version = 2]
+/// struct ResultsPage {
+///     genre: String,
+///     page: u32,
+/// }
+///
+/// let next_page = ResultsPage {
+///     genre: "Horror".to_owned(),
+///     page: 3,
+/// };
+/// let custom_id = next_page.encode().unwrap();
+/// assert_eq!(custom_id, "results:2:Horror:3");
+///
+/// // When the component is used, decode the state back from its custom ID
+/// let interaction = ComponentInteractionBuilder::new(&custom_id).build();
+/// assert_eq!(ResultsPage::parse(&interaction).unwrap(), next_page);
+///
+/// // State from a previous version of the struct is rejected
+/// assert!(matches!(
+///     ResultsPage::decode("results:1:Horror:3"),
+///     Err(ParseError::OutdatedCustomId { .. })
+/// ));
+/// ```
+/// To derive the trait, all fields must implement the [`parsable::CustomIdField`] trait. The state
+/// is encoded as the custom ID of the struct (the name of the struct, unless it is given via the
+/// `custom_id` attribute), then the version, then the values of the fields in order, separated by
+/// `:`. Field names are left out to keep it compact, so increase the version via the `version`
+/// attribute (which is 1 by default) whenever the fields change.
+///
+/// Discord only allows custom IDs of up to 100 characters. If every field has a maximum length
+/// (see [`parsable::CustomIdField::MAX_LENGTH`]), it is checked at compile time that the state will
+/// always fit. Otherwise, [`CustomIdCodec::encode`] returns an error when it doesn't.
+///
+/// To handle the components carrying the state, implement [`MessageComponentInteractionHandler`]
+/// for the struct and add it to a [`framework::ComponentRouter`].
+pub trait CustomIdCodec: Sized {
+    /// The custom ID identifying this kind of state
+    fn custom_id() -> String;
+    /// The version of the encoding, which must match for state to be decoded
+    fn version() -> u32;
+    /// Encode the values of the fields, in order
+    fn encode_fields(&self) -> Vec<String>;
+    /// Decode the values of the fields, or `None` if they are malformed
+    fn decode_fields(fields: &[&str]) -> Option<Self>;
+    /// Encode the state as a custom ID, or return an error if it is too long
+    fn encode(&self) -> Result<String, parsable::CustomIdTooLong> {
+        let mut parts = vec![Self::custom_id(), Self::version().to_string()];
+        parts.extend(self.encode_fields());
+        parsable::check_custom_id_length(parts.join(":"))
+    }
+    /// Decode state from a custom ID
+    ///
+    /// Returns a [`ParseError::UnknownComponent`] if the custom ID is for a different kind of state,
+    /// or a [`ParseError::OutdatedCustomId`] if it was encoded by a different version.
+    fn decode(custom_id: &str) -> Result<Self, ParseError> {
+        let mut parts = custom_id.split(':');
+        if parts.next() != Some(Self::custom_id().as_str()) {
+            return Err(ParseError::UnknownComponent {
+                custom_id: custom_id.to_owned(),
+            });
+        }
+        match parts.next().map(str::parse::<u32>) {
+            Some(Ok(version)) if version == Self::version() => (),
+            Some(Ok(_)) => {
+                return Err(ParseError::OutdatedCustomId {
+                    custom_id: custom_id.to_owned(),
+                })
+            }
+            _ => {
+                return Err(ParseError::InvalidCustomId {
+                    custom_id: custom_id.to_owned(),
+                })
+            }
+        }
+        Self::decode_fields(&parts.collect::<Vec<_>>()).ok_or_else(|| ParseError::InvalidCustomId {
+            custom_id: custom_id.to_owned(),
+        })
+    }
+    /// Decode the state stored in the custom ID of the component that was used in an interaction
+    fn parse(interaction: &MessageComponentInteraction) -> Result<Self, ParseError> {
+        Self::decode(&interaction.data.custom_id)
+    }
+}
+
+/// This trait provides the methods needed to create a select menu and parse the options the user
+/// selected.
+///
+/// For most use cases, just derive it via the macros crate on an enum with a unit variant for each
+/// option:
+/// ```
+/// # use slashies::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::prelude::*;
+/// # use serenity::model::interactions::InteractionResponseType;
+/// #[derive(Debug, PartialEq, SelectMenu)]
+/// #[custom_id = "genres"]
+/// #[placeholder = "Pick some genres"]
+/// #[min_values = 1]
+/// #[max_values = 2]
+/// enum Genre {
+///     /// Explosions and car chases
+///     #[emoji = "💥"]
+///     Action,
+///
+///     /// Spaceships and robots
+///     #[label = "Science fiction"]
+///     SciFi,
+///
+///     /// Things that go bump in the night
+///     Horror,
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// # let command = InteractionBuilder::new("recommend").build();
+/// command
+///     .create_interaction_response(&ctx.http, |response| {
+///         response
+///             .kind(InteractionResponseType::ChannelMessageWithSource)
+///             .interaction_response_data(|message| {
+///                 message
+///                     .content("What do you like to watch?")
+///                     .components(Genre::create_action_row)
+///             })
+///     })
+///     .await
+///     .unwrap();
+/// # let menu = &discord.interaction_responses()[0]["data"]["components"][0]["components"][0];
+/// # assert_eq!(menu["custom_id"], "genres");
+/// # assert_eq!(menu["max_values"], 2);
+/// # assert_eq!(menu["options"][1]["label"], "Science fiction");
+/// # assert_eq!(menu["options"][1]["description"], "Spaceships and robots");
+///
+/// // When the user makes a selection, parse it back into the enum
+/// let interaction = ComponentInteractionBuilder::new("genres")
+///     .values(["SciFi", "Horror"])
+///     .build();
+/// assert_eq!(Genre::parse(&interaction).unwrap(), vec![Genre::SciFi, Genre::Horror]);
+/// # }
+/// ```
+/// To derive the trait, you must provide a docstring for each variant, which will be used for the
+/// description of the option. The label of each option is the name of the variant, unless it is
+/// given via the `label` attribute, and an emoji can be shown next to it via the `emoji` attribute
+/// (see [`Buttons`]). The select menu's custom ID is the name of the enum, unless it is given via
+/// the `custom_id` attribute.
+///
+/// Discord's limits on select menus are checked at compile time: a select menu must have 1-25
+/// options, and labels and descriptions must be at most 100 characters long. You may also
+/// provide additional attributes for the select menu:
+///
+/// | Attribute   | Explanation                                                               | Examples                          |
+/// |-------------|---------------------------------------------------------------------------|-----------------------------------|
+/// | placeholder | Text shown in the menu while nothing is selected (at most 150 characters) | `#[placeholder = "Pick a genre"]` |
+/// | min_values  | The user must select at least this many options (0-25, 1 by default)      | `#[min_values = 0]`               |
+/// | max_values  | The user can select at most this many options (1-25, 1 by default)        | `#[max_values = 3]`               |
+///
+/// Selections can be handled by routing them to a [`framework::SelectMenuHandler`] with
+/// [`framework::ComponentRouter::select_menu`].
+pub trait SelectMenu: Sized {
+    /// The custom ID identifying this select menu
+    fn custom_id() -> String;
+    /// Try to parse the value of a selected option
+    fn parse_value(value: &str) -> Result<Self, ParseError>;
+    /// Fill in the custom ID, options and limits of the select menu
+    fn create(menu: &mut CreateSelectMenu) -> &mut CreateSelectMenu;
+    /// Try to parse the options selected in an interaction
+    ///
+    /// Returns a [`ParseError::UnknownComponent`] if the interaction is for a different component,
+    /// so that an interaction can be tried against each select menu in turn.
+    fn parse(interaction: &MessageComponentInteraction) -> Result<Vec<Self>, ParseError> {
+        if interaction.data.custom_id != Self::custom_id() {
+            return Err(ParseError::UnknownComponent {
+                custom_id: interaction.data.custom_id.clone(),
+            });
+        }
+        interaction
+            .data
+            .values
+            .iter()
+            .map(|value| Self::parse_value(value))
+            .collect()
+    }
+    /// Add an action row containing the select menu
+    fn create_action_row(components: &mut CreateComponents) -> &mut CreateComponents {
+        components.create_action_row(|row| row.create_select_menu(Self::create))
+    }
 }
 
 /// This trait provides a function to receive and respond to slash command interactions.
@@ -401,8 +1563,8 @@ pub trait SubCommandGroup: Sized {
 ///                     .kind(InteractionResponseType::ChannelMessageWithSource)
 ///                     .interaction_response_data(|message| message.content(greeting))
 ///             })
-///             .await
-///             .map_err(|_| InvocationError)
+///             .await?;
+///         Ok(())
 ///     }
 /// }
 /// ```
@@ -416,9 +1578,156 @@ pub trait ApplicationCommandInteractionHandler {
     ) -> Result<(), InvocationError>;
 }
 
+/// This trait provides a function to route autocomplete interactions to the handler for the option
+/// the user is currently filling in.
+///
+/// It is derived alongside [`Command`], [`SubCommand`] and [`SubCommandGroup`]. To provide
+/// suggestions for an option, point the `autocomplete` attribute at an async function that
+/// responds to the interaction (the path is given as a string, e.g.
+/// `#[autocomplete = "path::to::handler"]`):
+/// ```
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::interactions::*;
+/// # use serenity::model::interactions::autocomplete::AutocompleteInteraction;
+/// # use serenity::model::prelude::application_command::*;
+/// # use slashies::*;
+/// # use slashies_macros::*;
+/// async fn suggest_titles(
+///     ctx: &Context,
+///     interaction: &AutocompleteInteraction,
+///     option: &ApplicationCommandInteractionDataOption,
+/// ) -> Result<(), InvocationError> {
+///     let partial = option
+///         .value
+///         .as_ref()
+///         .and_then(|value| value.as_str())
+///         .unwrap_or_default()
+///         .to_owned();
+///     interaction
+///         .create_autocomplete_response(&ctx.http, |response| {
+///             response.add_string_choice(&partial, &partial)
+///         })
+///         .await?;
+///     Ok(())
+/// }
+///
+/// /// Search for a movie
+/// #[derive(Debug, Command)]
+/// #[name = "search"]
+/// struct SearchCommand {
+///     /// The title of the movie
+///     #[autocomplete = "suggest_titles"]
+///     title: String,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for SearchCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         unimplemented!()
+/// #     }
+/// # }
+/// ```
+///
+/// Incoming autocomplete interactions can then be dispatched via [`Commands::autocomplete`].
+///
+/// If you implement [`Command`], [`SubCommand`] or [`SubCommandGroup`] by hand, you don't need to
+/// implement this trait unless the options use autocomplete - in that case, implement it and forward
+/// `route_autocomplete` to it.
+#[async_trait]
+pub trait AutocompleteHandler {
+    /// Respond to an autocomplete interaction, given the options provided at this level of the
+    /// command
+    async fn autocomplete(
+        _ctx: &Context,
+        _interaction: &AutocompleteInteraction,
+        _options: &[ApplicationCommandInteractionDataOption],
+    ) -> Result<(), InvocationError> {
+        Err(InvocationError::new(
+            "no autocomplete handler for the focused option",
+        ))
+    }
+}
+
 /// This trait provides a function to receive and respond to message component interactions.
 ///
-/// TODO: more docs around handler map context
+/// Implement it for whatever holds the state that a message's components act on, and insert it
+/// into a [`sessions::ComponentSessions`] to route interactions on that message to it. The message
+/// the components are attached to is passed along so that it can be edited in response, e.g. to
+/// update a vote count.
+///
+/// Use [`Buttons::parse`] and [`SelectMenu::parse`] to find out which component was used. Much like
+/// commands are matched by name, these return [`ParseError::UnknownComponent`] when the custom ID
+/// belongs to a different component, so each set of components can be tried in turn:
+/// ```
+/// # use slashies::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::channel::Message;
+/// # use serenity::model::interactions::message_component::MessageComponentInteraction;
+/// #[derive(Debug, Buttons)]
+/// enum WatchlistButtons {
+///     /// Clear
+///     #[style(danger)]
+///     Clear,
+/// }
+///
+/// #[derive(Debug, SelectMenu)]
+/// #[max_values = 2]
+/// enum Genre {
+///     /// Explosions and car chases
+///     Action,
+///     /// Things that go bump in the night
+///     Horror,
+/// }
+///
+/// #[derive(Default)]
+/// struct Watchlist {
+///     genres: Vec<Genre>,
+/// }
+///
+/// #[async_trait]
+/// impl MessageComponentInteractionHandler for Watchlist {
+///     async fn invoke(
+///         &mut self,
+///         ctx: &Context,
+///         interaction: &MessageComponentInteraction,
+///         original_message: &mut Message,
+///     ) {
+///         match WatchlistButtons::parse(interaction) {
+///             Ok(WatchlistButtons::Clear) => self.genres.clear(),
+///             Err(ParseError::UnknownComponent { .. }) => {
+///                 if let Ok(genres) = Genre::parse(interaction) {
+///                     self.genres = genres;
+///                 }
+///             }
+///             Err(_) => (),
+///         }
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// let mut watchlist = Watchlist::default();
+/// let interaction = ComponentInteractionBuilder::new("Genre")
+///     .values(["Action", "Horror"])
+///     .build();
+/// let mut message = interaction.message.clone();
+/// watchlist.invoke(&ctx, &interaction, &mut message).await;
+/// assert_eq!(watchlist.genres.len(), 2);
+/// # }
+/// ```
+/// If the state a component acts on is stored in its custom ID via a [`CustomIdCodec`], implement
+/// this trait for the state itself and use a [`framework::ComponentRouter`] to route interactions to
+/// it.
 #[async_trait]
 pub trait MessageComponentInteractionHandler {
     /// Handle the message component interaction
@@ -433,7 +1742,13 @@ pub trait MessageComponentInteractionHandler {
 /// This trait should be derived for an enum with a variant for each command.
 /// This will implement the boilerplate to:
 /// - Parse an interaction into a specific command based on the command name
-/// - Delegate the invocation of a command to the specific enum variant
+/// - Evaluate the [checks](checks::Check) and enforce the [cooldowns](cooldown::Cooldown) for a
+///   command, then delegate its invocation to the specific enum variant
+/// - Route autocomplete interactions to the specific command
+/// - Register all of the commands at once (e.g. to build a [manifest](mod@manifest))
+///
+/// To run hooks (such as logging or timing) around every command, invoke the commands through a
+/// [`middleware::Dispatcher`] rather than calling [`Commands::invoke`] directly.
 ///
 /// ```
 /// # use slashies::*;
@@ -454,9 +1769,36 @@ pub trait MessageComponentInteractionHandler {
 /// #     unimplemented!()
 /// #     }
 /// # }
+/// # #[derive(Debug)]
+/// # struct PingCommand;
+/// # #[serenity::async_trait]
+/// # impl ApplicationCommandInteractionHandler for PingCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #     unimplemented!()
+/// #     }
+/// # }
+/// # impl Command for PingCommand {
+/// #     fn parse(_command: &ApplicationCommandInteraction) -> Result<Self, ParseError> {
+/// #         Ok(Self)
+/// #     }
+/// #     fn register(
+/// #         command: &mut serenity::builder::CreateApplicationCommand,
+/// #     ) -> &mut serenity::builder::CreateApplicationCommand {
+/// #         command.name("ping").description("Ping the bot")
+/// #     }
+/// #     fn name() -> String {
+/// #         "ping".to_owned()
+/// #     }
+/// # }
 /// #[derive(Debug, Commands)]
 /// enum BotCommands {
 ///     Hello(HelloCommand),
+///     // Implements Command by hand
+///     Ping(PingCommand),
 /// }
 /// ```
 #[async_trait]
@@ -464,12 +1806,22 @@ pub trait Commands: Sized {
     /// Parse an interaction into a specific command
     fn parse(ctx: &Context, command: &ApplicationCommandInteraction) -> Result<Self, ParseError>;
 
-    /// Invoke the command
+    /// Invoke the command, if its checks pass (see [`Command::run_checks`]) and it isn't on cooldown
+    /// (see [`Command::cooldowns`])
     async fn invoke(
         &self,
         ctx: &Context,
         command_interaction: &ApplicationCommandInteraction,
     ) -> Result<(), InvocationError>;
+
+    /// Route an autocomplete interaction to the handler for the focused option of the command
+    async fn autocomplete(
+        ctx: &Context,
+        interaction: &AutocompleteInteraction,
+    ) -> Result<(), InvocationError>;
+
+    /// Register every command in the enum
+    fn register(commands: &mut CreateApplicationCommands) -> &mut CreateApplicationCommands;
 }
 
 /// Register a set of commands (either globally or to a specific guild)
@@ -478,6 +1830,9 @@ pub trait Commands: Sized {
 /// flexibility to have some commands registered globally and others registered only in specific
 /// guilds.
 ///
+/// This overwrites all commands every time it's called. See [`sync_commands!`] for a version that
+/// only pushes the commands that changed.
+///
 /// Examples:
 /// ```no_run
 /// # use slashies::*;
@@ -563,3 +1918,134 @@ macro_rules! register_commands {
         }
     }};
 }
+
+/// Register a set of commands (either globally or to a specific guild), only creating, editing or
+/// deleting the commands that changed since they were last registered
+///
+/// This takes the same arguments as [`register_commands!`], but fetches the registered commands
+/// first and compares them with what [`Command::register`] produces. Commands that are already up
+/// to date keep their IDs and cost no extra requests. It returns a
+/// [`registration::RegistrationReport`] describing what was done - see
+/// [`registration::sync_commands`] for the details.
+///
+/// ```
+/// # use slashies::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// # use slashies_test::FakeDiscord;
+/// # /// Greet a user
+/// # #[derive(Debug, Command)]
+/// # #[name = "greet"]
+/// # struct HelloCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HelloCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #     unimplemented!()
+/// #     }
+/// # }
+/// # /// Another command
+/// # #[derive(Debug, Command)]
+/// # #[name = "next"]
+/// # struct NextCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for NextCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #     unimplemented!()
+/// #     }
+/// # }
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), serenity::Error> {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// let guild_id = Some(GuildId(1));
+///
+/// let report = sync_commands!(&ctx, guild_id, [HelloCommand, NextCommand])?;
+/// assert_eq!(report.created(), vec!["greet", "next"]);
+///
+/// // Nothing has changed, so nothing is sent to Discord
+/// let report = sync_commands!(&ctx, guild_id, [HelloCommand, NextCommand])?;
+/// assert!(!report.has_changes());
+///
+/// // Commands that aren't in the list any more are deleted
+/// let report = sync_commands!(&ctx, guild_id, [HelloCommand])?;
+/// assert_eq!(report.unchanged(), vec!["greet"]);
+/// assert_eq!(report.deleted(), vec!["next"]);
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! sync_commands {
+    ($ctx:expr, $guild_id:expr, [$($cmdType:ty),+]) => {{
+        let commands = vec![
+            $({
+                let mut command = serenity::builder::CreateApplicationCommand::default();
+                <$cmdType as slashies::Command>::register(&mut command);
+                command
+            }),+
+        ];
+        slashies::registration::sync_commands(&$ctx.http, $guild_id, &commands).await
+    }};
+}
+
+/// Build the JSON body of Discord's bulk overwrite endpoint for a set of commands, without
+/// needing a [`Context`]
+///
+/// This takes the same list of commands as [`register_commands!`]. To build the manifest for every
+/// command in a [`Commands`] enum, use [`manifest::commands`] instead.
+///
+/// ```
+/// # use slashies::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// /// Greet a user
+/// #[derive(Debug, Command)]
+/// #[name = "greet"]
+/// struct HelloCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HelloCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #     unimplemented!()
+/// #     }
+/// # }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let manifest = manifest!([HelloCommand]);
+/// assert_eq!(manifest[0]["name"], "greet");
+///
+/// // Write the manifest out to review it or register it later
+/// let json = serde_json::to_string_pretty(&manifest).unwrap();
+/// # // The manifest is exactly what register_commands! sends
+/// # let discord = slashies_test::FakeDiscord::start().await;
+/// # register_commands!(&discord.context(), None, [HelloCommand]).unwrap();
+/// # assert_eq!(discord.requests()[0].body, manifest);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! manifest {
+    ([$($cmdType:ty),+]) => {{
+        let mut commands = serenity::builder::CreateApplicationCommands::default();
+        commands
+        $(
+            .create_application_command(|command| <$cmdType as slashies::Command>::register(command))
+        )*;
+        slashies::manifest::from_builder(commands)
+    }};
+}
@@ -0,0 +1,160 @@
+use serenity::{
+    builder::{CreateApplicationCommand, CreateApplicationCommandOption},
+    json::{json, JsonMap, Value},
+};
+
+/// The locales Discord supports for localized names and descriptions
+pub const LOCALES: [&str; 32] = [
+    "id", "da", "de", "en-GB", "en-US", "es-ES", "es-419", "fr", "hr", "it", "lt", "hu", "nl",
+    "no", "pl", "pt-BR", "ro", "fi", "sv-SE", "vi", "tr", "cs", "el", "bg", "ru", "uk", "hi", "th",
+    "zh-CN", "ja", "zh-TW", "ko",
+];
+
+/// Adds localized names and descriptions to serenity's command builders
+///
+/// The derive macros use this for the `name_localized` and `description_localized` attributes, but
+/// it can also be used when implementing [`Command`](crate::Command) or
+/// [`SubCommand`](crate::SubCommand) by hand:
+/// ```
+/// # use serenity::builder::CreateApplicationCommand;
+/// use slashies::localization::Localize;
+///
+/// let mut command = CreateApplicationCommand::default();
+/// command
+///     .name("greet")
+///     .name_localized("de", "grüßen")
+///     .description("Greet a user")
+///     .description_localized("de", "Einen Benutzer grüßen");
+/// assert_eq!(command.0["name_localizations"]["de"], "grüßen");
+/// ```
+pub trait Localize {
+    /// Add a name for the given locale (e.g. `"de"` or `"pt-BR"`)
+    fn name_localized(&mut self, locale: &str, name: &str) -> &mut Self;
+    /// Add a description for the given locale (e.g. `"de"` or `"pt-BR"`)
+    fn description_localized(&mut self, locale: &str, description: &str) -> &mut Self;
+}
+
+impl Localize for CreateApplicationCommand {
+    fn name_localized(&mut self, locale: &str, name: &str) -> &mut Self {
+        insert_localization(self.0.entry("name_localizations"), locale, name);
+        self
+    }
+
+    fn description_localized(&mut self, locale: &str, description: &str) -> &mut Self {
+        insert_localization(
+            self.0.entry("description_localizations"),
+            locale,
+            description,
+        );
+        self
+    }
+}
+
+impl Localize for CreateApplicationCommandOption {
+    fn name_localized(&mut self, locale: &str, name: &str) -> &mut Self {
+        insert_localization(self.0.entry("name_localizations"), locale, name);
+        self
+    }
+
+    fn description_localized(&mut self, locale: &str, description: &str) -> &mut Self {
+        insert_localization(
+            self.0.entry("description_localizations"),
+            locale,
+            description,
+        );
+        self
+    }
+}
+
+/// Adds choices with localized names to serenity's command option builder
+///
+/// The derive macros use this for `name_localized` inside the `choice` attribute (e.g.
+/// `#[choice("Action", name_localized("de", "Aktion"))]`).
+pub trait LocalizeChoices {
+    /// Add a string choice with names for other locales, given as `(locale, name)` pairs
+    fn add_string_choice_localized(
+        &mut self,
+        name: &str,
+        value: &str,
+        localizations: &[(&str, &str)],
+    ) -> &mut Self;
+    /// Add an integer choice with names for other locales, given as `(locale, name)` pairs
+    fn add_int_choice_localized(
+        &mut self,
+        name: &str,
+        value: i32,
+        localizations: &[(&str, &str)],
+    ) -> &mut Self;
+    /// Add a number choice with names for other locales, given as `(locale, name)` pairs
+    fn add_number_choice_localized(
+        &mut self,
+        name: &str,
+        value: f64,
+        localizations: &[(&str, &str)],
+    ) -> &mut Self;
+}
+
+impl LocalizeChoices for CreateApplicationCommandOption {
+    fn add_string_choice_localized(
+        &mut self,
+        name: &str,
+        value: &str,
+        localizations: &[(&str, &str)],
+    ) -> &mut Self {
+        add_localized_choice(self, name, json!(value), localizations)
+    }
+
+    fn add_int_choice_localized(
+        &mut self,
+        name: &str,
+        value: i32,
+        localizations: &[(&str, &str)],
+    ) -> &mut Self {
+        add_localized_choice(self, name, json!(value), localizations)
+    }
+
+    fn add_number_choice_localized(
+        &mut self,
+        name: &str,
+        value: f64,
+        localizations: &[(&str, &str)],
+    ) -> &mut Self {
+        add_localized_choice(self, name, json!(value), localizations)
+    }
+}
+
+fn insert_localization(
+    entry: std::collections::hash_map::Entry<&'static str, Value>,
+    locale: &str,
+    text: &str,
+) {
+    if let Some(localizations) = entry.or_insert_with(|| json!({})).as_object_mut() {
+        localizations.insert(locale.to_owned(), json!(text));
+    }
+}
+
+fn add_localized_choice<'a>(
+    option: &'a mut CreateApplicationCommandOption,
+    name: &str,
+    value: Value,
+    localizations: &[(&str, &str)],
+) -> &'a mut CreateApplicationCommandOption {
+    let name_localizations: JsonMap = localizations
+        .iter()
+        .map(|(locale, name)| (locale.to_string(), json!(name)))
+        .collect();
+    let choice = json!({
+        "name": name,
+        "value": value,
+        "name_localizations": name_localizations,
+    });
+    if let Some(choices) = option
+        .0
+        .entry("choices")
+        .or_insert_with(|| json!([]))
+        .as_array_mut()
+    {
+        choices.push(choice);
+    }
+    option
+}
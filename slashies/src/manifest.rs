@@ -0,0 +1,82 @@
+use serenity::{
+    builder::{CreateApplicationCommand, CreateApplicationCommands},
+    json::{hashmap_to_json_map, Value},
+};
+
+use crate::{Command, Commands};
+
+/// Get the JSON for a single command, exactly as it's sent to Discord when registering it
+pub fn command<C: Command>() -> Value {
+    let mut command = CreateApplicationCommand::default();
+    C::register(&mut command);
+    Value::Object(hashmap_to_json_map(command.0))
+}
+
+/// Get the JSON body of Discord's bulk overwrite endpoint for every command in a [`Commands`] enum
+///
+/// The result is exactly what [`register_commands!`](crate::register_commands!) would send if it
+/// was given every command in the enum, so it can be committed to review changes to commands, or
+/// used to register the commands from a separate deployment step.
+///
+/// ```
+/// # use slashies::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// # use slashies::parsable::*;
+/// /// Greet a user
+/// #[derive(Debug, Command)]
+/// #[name = "greet"]
+/// struct HelloCommand {
+///     /// The user to greet
+///     user: UserInput,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HelloCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #     unimplemented!()
+/// #     }
+/// # }
+///
+/// #[derive(Debug, UserCommand)]
+/// #[name = "High Five"]
+/// struct HighFiveCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HighFiveCommand {
+/// #    async fn invoke(
+/// #        &self,
+/// #        ctx: &Context,
+/// #        command: &ApplicationCommandInteraction,
+/// #    ) -> Result<(), InvocationError> {
+/// #     unimplemented!()
+/// #     }
+/// # }
+///
+/// #[derive(Debug, Commands)]
+/// enum BotCommands {
+///     Hello(HelloCommand),
+///     HighFive(HighFiveCommand),
+/// }
+///
+/// let manifest = slashies::manifest::commands::<BotCommands>();
+/// assert_eq!(manifest[0]["name"], "greet");
+/// assert_eq!(manifest[0]["options"][0]["name"], "user");
+/// assert_eq!(manifest[1]["name"], "High Five");
+/// assert_eq!(manifest[1]["type"], 2);
+/// ```
+pub fn commands<C: Commands>() -> Value {
+    let mut commands = CreateApplicationCommands::default();
+    C::register(&mut commands);
+    from_builder(commands)
+}
+
+/// Get the JSON body of Discord's bulk overwrite endpoint for commands that have been added to a
+/// serenity builder
+pub fn from_builder(commands: CreateApplicationCommands) -> Value {
+    Value::Array(commands.0)
+}
@@ -0,0 +1,429 @@
+use crate::{Commands, InvocationError};
+use serenity::{
+    async_trait,
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOption,
+        ApplicationCommandOptionType,
+    },
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// Hooks that run around every command invoked through a [`Dispatcher`], for things like logging,
+/// timing and replying to errors without touching every handler
+///
+/// Each hook receives the name path of the command being invoked, e.g. `["permissions", "user",
+/// "edit"]` for the `edit` subcommand of the `user` group of the `permissions` command. All hooks
+/// do nothing by default, so only the ones that are needed have to be implemented.
+///
+/// [`Middleware::after`] and [`Middleware::on_error`] are only called if [`Middleware::before`] was
+/// called for the same invocation, so state set up in `before` (such as a timer or a tracing span)
+/// can always be cleaned up.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Called before the command is invoked. Returning an error stops the command (and any
+    /// middleware later in the stack) from running, and the error is passed to
+    /// [`Middleware::on_error`] of this middleware and those earlier in the stack instead.
+    async fn before(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+        _path: &[String],
+    ) -> Result<(), InvocationError> {
+        Ok(())
+    }
+
+    /// Called after the command was invoked successfully
+    async fn after(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+        _path: &[String],
+    ) {
+    }
+
+    /// Called when a check failed, invoking the command returned an error, or a `before` hook
+    /// returned an error
+    async fn on_error(
+        &self,
+        _ctx: &Context,
+        _command: &ApplicationCommandInteraction,
+        _path: &[String],
+        _error: &InvocationError,
+    ) {
+    }
+}
+
+/// Parses and invokes [`Commands`], running a stack of [`Middleware`] around each invocation
+///
+/// The `before` hooks run in the order the middleware was added, and the `after` and `on_error`
+/// hooks run in reverse order, so that the first middleware added wraps all the others:
+/// ```
+/// # use slashies::*;
+/// # use slashies::middleware::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// # /// Greet a user
+/// # #[derive(Debug, Command)]
+/// # #[name = "greet"]
+/// # struct HelloCommand;
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for HelloCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// #[derive(Debug, Commands)]
+/// enum BotCommands {
+///     Hello(HelloCommand),
+/// }
+///
+/// struct Logger;
+///
+/// #[async_trait]
+/// impl Middleware for Logger {
+///     async fn after(
+///         &self,
+///         _ctx: &Context,
+///         _command: &ApplicationCommandInteraction,
+///         path: &[String],
+///     ) {
+///         println!("/{} succeeded", path.join(" "));
+///     }
+///
+///     async fn on_error(
+///         &self,
+///         _ctx: &Context,
+///         _command: &ApplicationCommandInteraction,
+///         path: &[String],
+///         error: &InvocationError,
+///     ) {
+///         println!("/{} failed: {}", path.join(" "), error);
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// let dispatcher = Dispatcher::<BotCommands>::new().with(Logger);
+/// let interaction = InteractionBuilder::new("greet").build();
+/// dispatcher
+///     .dispatch(&ctx, &interaction)
+///     .await
+///     .expect("Failed to invoke command");
+/// # }
+/// ```
+pub struct Dispatcher<C> {
+    middleware: Vec<Arc<dyn Middleware>>,
+    commands: PhantomData<fn() -> C>,
+}
+
+impl<C: Commands> Dispatcher<C> {
+    /// Create a dispatcher without any middleware
+    pub fn new() -> Self {
+        Self {
+            middleware: Vec::new(),
+            commands: PhantomData,
+        }
+    }
+
+    /// Add a middleware to the end of the stack
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Parse the interaction into a command and invoke it, running the middleware around it
+    ///
+    /// Errors from invoking the command are passed to the `on_error` hooks and then returned. Parse
+    /// errors are returned straight away, as no middleware has run yet.
+    pub async fn dispatch(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+    ) -> Result<(), InvocationError> {
+        let parsed = C::parse(ctx, command)?;
+        let path = command_path(command);
+        // Only the middleware whose `before` hook ran get to see how the invocation went
+        let mut entered = 0;
+        let mut result = Ok(());
+        for middleware in &self.middleware {
+            entered += 1;
+            result = middleware.before(ctx, command, &path).await;
+            if result.is_err() {
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = parsed.invoke(ctx, command).await;
+        }
+        let entered = &self.middleware[..entered];
+        match &result {
+            Ok(()) => {
+                for middleware in entered.iter().rev() {
+                    middleware.after(ctx, command, &path).await;
+                }
+            }
+            Err(error) => {
+                for middleware in entered.iter().rev() {
+                    middleware.on_error(ctx, command, &path, error).await;
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<C: Commands> Default for Dispatcher<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Clone for Dispatcher<C> {
+    fn clone(&self) -> Self {
+        Self {
+            middleware: self.middleware.clone(),
+            commands: PhantomData,
+        }
+    }
+}
+
+/// The name path of the command being invoked: the command's name followed by the names of the
+/// subcommand group and subcommand being invoked (if any)
+pub fn command_path(command: &ApplicationCommandInteraction) -> Vec<String> {
+    let mut path = vec![command.data.name.clone()];
+    let mut options = &command.data.options;
+    while let Some(option) = options.iter().find(|option| is_subcommand(option)) {
+        path.push(option.name.clone());
+        options = &option.options;
+    }
+    path
+}
+
+fn is_subcommand(option: &ApplicationCommandInteractionDataOption) -> bool {
+    matches!(
+        option.kind,
+        ApplicationCommandOptionType::SubCommand | ApplicationCommandOptionType::SubCommandGroup
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{testing::*, ApplicationCommandInteractionHandler, ParseError};
+    use serenity::builder::CreateApplicationCommands;
+    use serenity::model::interactions::autocomplete::AutocompleteInteraction;
+    use slashies_test::FakeDiscord;
+    use std::sync::Mutex;
+
+    /// A command that fails when invoked with `fail: true`
+    struct TestCommands {
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl Commands for TestCommands {
+        fn parse(
+            _ctx: &Context,
+            command: &ApplicationCommandInteraction,
+        ) -> Result<Self, ParseError> {
+            match command.data.name.as_str() {
+                "test" => Ok(Self {
+                    fail: command
+                        .data
+                        .options
+                        .iter()
+                        .any(|option| option.name == "fail"),
+                }),
+                name => Err(ParseError::UnknownCommand {
+                    name: name.to_owned(),
+                }),
+            }
+        }
+
+        async fn invoke(
+            &self,
+            ctx: &Context,
+            command: &ApplicationCommandInteraction,
+        ) -> Result<(), InvocationError> {
+            ApplicationCommandInteractionHandler::invoke(self, ctx, command).await
+        }
+
+        async fn autocomplete(
+            _ctx: &Context,
+            _interaction: &AutocompleteInteraction,
+        ) -> Result<(), InvocationError> {
+            Ok(())
+        }
+
+        fn register(commands: &mut CreateApplicationCommands) -> &mut CreateApplicationCommands {
+            commands
+        }
+    }
+
+    #[async_trait]
+    impl ApplicationCommandInteractionHandler for TestCommands {
+        async fn invoke(
+            &self,
+            _ctx: &Context,
+            _command: &ApplicationCommandInteraction,
+        ) -> Result<(), InvocationError> {
+            match self.fail {
+                true => Err(InvocationError::new("failed")),
+                false => Ok(()),
+            }
+        }
+    }
+
+    /// Records every hook that runs, labelled with the name of the middleware
+    struct Recorder {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<String>>>,
+        reject: bool,
+    }
+
+    #[async_trait]
+    impl Middleware for Recorder {
+        async fn before(
+            &self,
+            _ctx: &Context,
+            _command: &ApplicationCommandInteraction,
+            path: &[String],
+        ) -> Result<(), InvocationError> {
+            let call = format!("{} before {}", self.name, path.join(" "));
+            self.calls.lock().unwrap().push(call);
+            match self.reject {
+                true => Err(InvocationError::new("rejected")),
+                false => Ok(()),
+            }
+        }
+
+        async fn after(
+            &self,
+            _ctx: &Context,
+            _command: &ApplicationCommandInteraction,
+            path: &[String],
+        ) {
+            let call = format!("{} after {}", self.name, path.join(" "));
+            self.calls.lock().unwrap().push(call);
+        }
+
+        async fn on_error(
+            &self,
+            _ctx: &Context,
+            _command: &ApplicationCommandInteraction,
+            path: &[String],
+            error: &InvocationError,
+        ) {
+            let call = format!("{} on_error {}: {}", self.name, path.join(" "), error);
+            self.calls.lock().unwrap().push(call);
+        }
+    }
+
+    fn recorder(name: &'static str, calls: &Arc<Mutex<Vec<String>>>) -> Recorder {
+        Recorder {
+            name,
+            calls: calls.clone(),
+            reject: false,
+        }
+    }
+
+    fn dispatcher(calls: &Arc<Mutex<Vec<String>>>) -> Dispatcher<TestCommands> {
+        Dispatcher::new()
+            .with(recorder("outer", calls))
+            .with(recorder("inner", calls))
+    }
+
+    #[tokio::test]
+    async fn middleware_wraps_the_command_in_order() {
+        let discord = FakeDiscord::start().await;
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let interaction = InteractionBuilder::new("test")
+            .option(
+                OptionBuilder::sub_command_group("group").option(OptionBuilder::sub_command("run")),
+            )
+            .build();
+
+        dispatcher(&calls)
+            .dispatch(&discord.context(), &interaction)
+            .await
+            .unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "outer before test group run",
+                "inner before test group run",
+                "inner after test group run",
+                "outer after test group run",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_are_passed_to_on_error() {
+        let discord = FakeDiscord::start().await;
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let interaction = InteractionBuilder::new("test")
+            .option(OptionBuilder::boolean("fail", true))
+            .build();
+
+        let result = dispatcher(&calls)
+            .dispatch(&discord.context(), &interaction)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "outer before test",
+                "inner before test",
+                "inner on_error test: failed",
+                "outer on_error test: failed",
+            ]
+        );
+
+        // No middleware has run when parsing fails, so none of them see the error
+        calls.lock().unwrap().clear();
+        let interaction = InteractionBuilder::new("unknown").build();
+        let result = dispatcher(&calls)
+            .dispatch(&discord.context(), &interaction)
+            .await;
+        assert!(result.is_err());
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn only_middleware_that_ran_see_the_outcome() {
+        let discord = FakeDiscord::start().await;
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let interaction = InteractionBuilder::new("test").build();
+        let dispatcher = Dispatcher::<TestCommands>::new()
+            .with(recorder("outer", &calls))
+            .with(Recorder {
+                reject: true,
+                ..recorder("guard", &calls)
+            })
+            .with(recorder("inner", &calls));
+
+        let result = dispatcher.dispatch(&discord.context(), &interaction).await;
+        assert_eq!(result.unwrap_err().to_string(), "rejected");
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "outer before test",
+                "guard before test",
+                "guard on_error test: rejected",
+                "outer on_error test: rejected",
+            ]
+        );
+    }
+}
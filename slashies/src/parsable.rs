@@ -1,14 +1,25 @@
-use serenity::model::{
-    channel::PartialChannel,
-    guild::{PartialMember, Role},
-    interactions::application_command::{
-        ApplicationCommandInteractionDataOption, ApplicationCommandInteractionDataOptionValue,
-        ApplicationCommandOptionType,
+use serenity::{
+    builder::CreateApplicationCommandOption,
+    model::{
+        channel::{Attachment, Message, PartialChannel},
+        guild::{PartialMember, Role},
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+        interactions::application_command::{
+            ApplicationCommandInteraction, ApplicationCommandInteractionDataOption,
+            ApplicationCommandInteractionDataOptionValue, ApplicationCommandOptionType,
+            ApplicationCommandType, ResolvedTarget,
+        },
+        interactions::{message_component::ActionRowComponent, modal::ModalSubmitInteraction},
+        prelude::User,
     },
-    prelude::User,
 };
 
-use crate::ParseError;
+use crate::{InvocationError, ParseError};
+use std::{
+    error::Error,
+    fmt,
+    num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize},
+};
 
 /// This trait contains the functions needed to parse/register a command option
 ///
@@ -16,20 +27,70 @@ use crate::ParseError;
 ///
 /// The following types are implemented out of the box:
 ///
-/// | Discord type | Rust type          |
-/// |--------------|--------------------|
-/// | STRING       | [`String`]         |
-/// | INTEGER      | [`i64`]            |
-/// | BOOLEAN      | [`bool`]           |
-/// | USER         | [`UserInput`]      |
-/// | CHANNEL      | [`PartialChannel`] |
-/// | ROLE         | [`Mentionable`]    |
-/// | NUMBER       | [`f64`]            |
-/// | ATTACHMENT   | N/A                |
+/// | Discord type | Rust type                                                                       |
+/// |--------------|---------------------------------------------------------------------------------|
+/// | STRING       | [`String`]                                                                      |
+/// | INTEGER      | [`i64`], [`i32`], [`i16`], [`i8`], [`u64`], [`u32`], [`u16`], [`u8`], [`usize`] |
+/// | INTEGER      | [`NonZeroU64`], [`NonZeroU32`], [`NonZeroU16`], [`NonZeroU8`], [`NonZeroUsize`] |
+/// | BOOLEAN      | [`bool`]                                                                        |
+/// | USER         | [`UserInput`]                                                                   |
+/// | CHANNEL      | [`PartialChannel`]                                                              |
+/// | ROLE         | [`Mentionable`]                                                                 |
+/// | NUMBER       | [`f64`], [`f32`]                                                                |
+/// | ATTACHMENT   | [`AttachmentInput`]                                                             |
+///
+/// Integer types other than [`i64`], and [`f32`], are registered with the smallest and largest
+/// values they can hold (within the range Discord allows), so users can't enter a value that
+/// doesn't fit. If one is received anyway, parsing fails with a [`ParseError::OutOfRange`]:
+/// ```
+/// # use slashies::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// /// Rate a movie
+/// #[derive(Debug, Command)]
+/// #[name = "rate"]
+/// struct RateCommand {
+///     /// Your rating
+///     #[max = 10]
+///     stars: u8,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for RateCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         unimplemented!()
+/// #     }
+/// # }
+///
+/// let registered = manifest::command::<RateCommand>();
+/// assert_eq!(registered["options"][0]["min_value"], 0);
+/// assert_eq!(registered["options"][0]["max_value"], 10);
+///
+/// let interaction = InteractionBuilder::new("rate")
+///     .option(OptionBuilder::integer("stars", -1))
+///     .build();
+/// let error = RateCommand::parse(&interaction).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "option `rate stars` must be between 0 and 255 (received -1)"
+/// );
+/// ```
+/// To restrict the range of an INTEGER or NUMBER option further, use the `min` and `max`
+/// attributes (as above), or [`BoundedInt`] or [`BoundedNumber`] to have the range checked when
+/// parsing too. The two can't be combined on one option.
 pub trait ParsableCommandOption: Sized {
     /// Try to parse this from a command argument provided by an interaction.
     /// The argument might not have been provided, hence the optional input - if this is a
     /// non-optional type we would normally return a [`ParseError::MissingOption`] in this case.
+    ///
+    /// There is no need to fill in the path of the option in any errors returned - the derive
+    /// macros will add it as the error is passed back up through the command.
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError>;
@@ -42,20 +103,56 @@ pub trait ParsableCommandOption: Sized {
     fn is_required() -> bool {
         true
     }
+
+    /// Add any restrictions this type places on the option when registering it, such as a minimum
+    /// and maximum value. Defaults to adding nothing.
+    ///
+    /// Attributes on the option (e.g. `#[min = 1]`) are applied afterwards, so they take precedence.
+    fn register_option(
+        option: &mut CreateApplicationCommandOption,
+    ) -> &mut CreateApplicationCommandOption {
+        option
+    }
+
+    /// Whether the range of the option is fixed by the type, as for [`BoundedInt`] and
+    /// [`BoundedNumber`]. The derive macros refuse to compile `min` and `max` attributes on these
+    /// options, as Discord would no longer enforce the range that is checked when parsing.
+    const HAS_BOUNDS: bool = false;
+}
+
+/// This trait contains the function needed to parse the target of a user or message command (i.e.
+/// the user or message the command was invoked on via the context menu)
+///
+/// The following types are implemented out of the box:
+///
+/// | Discord command type | Rust type     |
+/// |----------------------|---------------|
+/// | USER                 | [`UserInput`] |
+/// | MESSAGE              | [`Message`]   |
+pub trait ParsableTarget: Sized {
+    /// Try to parse this from the resolved target of an interaction
+    fn parse_target(command: &ApplicationCommandInteraction) -> Result<Self, ParseError>;
+}
+
+/// Fetch the resolved value of an option, or a [`ParseError::MissingOption`] if it wasn't provided
+fn resolved_value<T: ParsableCommandOption>(
+    option: Option<&ApplicationCommandInteractionDataOption>,
+) -> Result<ApplicationCommandInteractionDataOptionValue, ParseError> {
+    option
+        .and_then(|option| option.resolved.clone())
+        .ok_or_else(|| ParseError::missing_option(T::application_command_option_type()))
 }
 
 impl ParsableCommandOption for String {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError> {
-        match option
-            .ok_or(ParseError::MissingOption)?
-            .resolved
-            .clone()
-            .ok_or(ParseError::MissingOption)?
-        {
+        match resolved_value::<Self>(option)? {
             ApplicationCommandInteractionDataOptionValue::String(s) => Ok(s),
-            _ => Err(ParseError::InvalidOption),
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
         }
     }
 
@@ -68,14 +165,12 @@ impl ParsableCommandOption for i64 {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError> {
-        match option
-            .ok_or(ParseError::MissingOption)?
-            .resolved
-            .clone()
-            .ok_or(ParseError::MissingOption)?
-        {
+        match resolved_value::<Self>(option)? {
             ApplicationCommandInteractionDataOptionValue::Integer(i) => Ok(i),
-            _ => Err(ParseError::InvalidOption),
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
         }
     }
 
@@ -88,14 +183,12 @@ impl ParsableCommandOption for bool {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError> {
-        match option
-            .ok_or(ParseError::MissingOption)?
-            .resolved
-            .clone()
-            .ok_or(ParseError::MissingOption)?
-        {
+        match resolved_value::<Self>(option)? {
             ApplicationCommandInteractionDataOptionValue::Boolean(b) => Ok(b),
-            _ => Err(ParseError::InvalidOption),
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
         }
     }
 
@@ -117,17 +210,15 @@ impl ParsableCommandOption for UserInput {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError> {
-        match option
-            .ok_or(ParseError::MissingOption)?
-            .resolved
-            .clone()
-            .ok_or(ParseError::MissingOption)?
-        {
+        match resolved_value::<Self>(option)? {
             ApplicationCommandInteractionDataOptionValue::User(u, pm) => Ok(UserInput {
                 user: u,
                 member: pm,
             }),
-            _ => Err(ParseError::InvalidOption),
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
         }
     }
 
@@ -140,14 +231,12 @@ impl ParsableCommandOption for PartialChannel {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError> {
-        match option
-            .ok_or(ParseError::MissingOption)?
-            .resolved
-            .clone()
-            .ok_or(ParseError::MissingOption)?
-        {
+        match resolved_value::<Self>(option)? {
             ApplicationCommandInteractionDataOptionValue::Channel(c) => Ok(c),
-            _ => Err(ParseError::InvalidOption),
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
         }
     }
 
@@ -160,14 +249,12 @@ impl ParsableCommandOption for Role {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError> {
-        match option
-            .ok_or(ParseError::MissingOption)?
-            .resolved
-            .clone()
-            .ok_or(ParseError::MissingOption)?
-        {
+        match resolved_value::<Self>(option)? {
             ApplicationCommandInteractionDataOptionValue::Role(r) => Ok(r),
-            _ => Err(ParseError::InvalidOption),
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
         }
     }
 
@@ -176,6 +263,31 @@ impl ParsableCommandOption for Role {
     }
 }
 
+impl ParsableTarget for UserInput {
+    fn parse_target(command: &ApplicationCommandInteraction) -> Result<Self, ParseError> {
+        match command.data.target() {
+            Some(ResolvedTarget::User(u, pm)) => Ok(UserInput {
+                user: u,
+                member: pm.map(|pm| *pm),
+            }),
+            _ => Err(ParseError::InvalidTarget {
+                expected: ApplicationCommandType::User,
+            }),
+        }
+    }
+}
+
+impl ParsableTarget for Message {
+    fn parse_target(command: &ApplicationCommandInteraction) -> Result<Self, ParseError> {
+        match command.data.target() {
+            Some(ResolvedTarget::Message(m)) => Ok(*m),
+            _ => Err(ParseError::InvalidTarget {
+                expected: ApplicationCommandType::Message,
+            }),
+        }
+    }
+}
+
 /// An input for the MENTIONABLE Discord type
 /// Will either be a role or a user
 #[derive(Debug, Clone)]
@@ -190,12 +302,7 @@ impl ParsableCommandOption for Mentionable {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError> {
-        match option
-            .ok_or(ParseError::MissingOption)?
-            .resolved
-            .clone()
-            .ok_or(ParseError::MissingOption)?
-        {
+        match resolved_value::<Self>(option)? {
             ApplicationCommandInteractionDataOptionValue::Role(r) => Ok(Self::Role(r)),
             ApplicationCommandInteractionDataOptionValue::User(u, pm) => {
                 Ok(Self::User(UserInput {
@@ -203,7 +310,10 @@ impl ParsableCommandOption for Mentionable {
                     member: pm,
                 }))
             }
-            _ => Err(ParseError::InvalidOption),
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
         }
     }
 
@@ -216,14 +326,12 @@ impl ParsableCommandOption for f64 {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
     ) -> Result<Self, ParseError> {
-        match option
-            .ok_or(ParseError::MissingOption)?
-            .resolved
-            .clone()
-            .ok_or(ParseError::MissingOption)?
-        {
+        match resolved_value::<Self>(option)? {
             ApplicationCommandInteractionDataOptionValue::Number(n) => Ok(n),
-            _ => Err(ParseError::InvalidOption),
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
         }
     }
 
@@ -232,6 +340,365 @@ impl ParsableCommandOption for f64 {
     }
 }
 
+/// Discord only accepts INTEGER and NUMBER values between -2^53 and 2^53, all of which can be
+/// represented exactly as an [`f64`]
+const MAX_DISCORD_INTEGER: i64 = 1 << 53;
+
+/// Narrow the range of values a type can hold to the range Discord accepts
+const fn discord_bounds(min: i128, max: i128) -> (i64, i64) {
+    let limit = MAX_DISCORD_INTEGER as i128;
+    let min = if min < -limit { -limit } else { min };
+    let max = if max > limit { limit } else { max };
+    (min as i64, max as i64)
+}
+
+/// Parse an INTEGER option, checking that its value is within the given bounds
+fn parse_integer(
+    option: Option<&ApplicationCommandInteractionDataOption>,
+    (min, max): (i64, i64),
+) -> Result<i64, ParseError> {
+    let value = i64::parse_from(option)?;
+    if (min..=max).contains(&value) {
+        Ok(value)
+    } else {
+        Err(ParseError::out_of_range(
+            value as f64,
+            min as f64,
+            max as f64,
+        ))
+    }
+}
+
+macro_rules! impl_parsable_command_option_for_integer {
+    ($($int_type:ty),*) => {
+        $(
+            impl ParsableCommandOption for $int_type {
+                fn parse_from(
+                    option: Option<&ApplicationCommandInteractionDataOption>,
+                ) -> Result<Self, ParseError> {
+                    let bounds =
+                        discord_bounds(<$int_type>::MIN as i128, <$int_type>::MAX as i128);
+                    parse_integer(option, bounds).map(|value| value as $int_type)
+                }
+
+                fn application_command_option_type() -> ApplicationCommandOptionType {
+                    ApplicationCommandOptionType::Integer
+                }
+
+                fn register_option(
+                    option: &mut CreateApplicationCommandOption,
+                ) -> &mut CreateApplicationCommandOption {
+                    let (min, max) =
+                        discord_bounds(<$int_type>::MIN as i128, <$int_type>::MAX as i128);
+                    option.min_int_value(min).max_int_value(max)
+                }
+            }
+        )*
+    };
+}
+
+impl_parsable_command_option_for_integer!(i8, i16, i32, u8, u16, u32, u64, usize);
+
+macro_rules! impl_parsable_command_option_for_non_zero {
+    ($($non_zero_type:ty => $int_type:ty),*) => {
+        $(
+            impl ParsableCommandOption for $non_zero_type {
+                fn parse_from(
+                    option: Option<&ApplicationCommandInteractionDataOption>,
+                ) -> Result<Self, ParseError> {
+                    let bounds = discord_bounds(1, <$int_type>::MAX as i128);
+                    let value = parse_integer(option, bounds)?;
+                    Ok(<$non_zero_type>::new(value as $int_type)
+                        .expect("the bounds start at 1, so the value can't be 0"))
+                }
+
+                fn application_command_option_type() -> ApplicationCommandOptionType {
+                    ApplicationCommandOptionType::Integer
+                }
+
+                fn register_option(
+                    option: &mut CreateApplicationCommandOption,
+                ) -> &mut CreateApplicationCommandOption {
+                    let (min, max) = discord_bounds(1, <$int_type>::MAX as i128);
+                    option.min_int_value(min).max_int_value(max)
+                }
+            }
+        )*
+    };
+}
+
+impl_parsable_command_option_for_non_zero!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroUsize => usize
+);
+
+impl ParsableCommandOption for f32 {
+    fn parse_from(
+        option: Option<&ApplicationCommandInteractionDataOption>,
+    ) -> Result<Self, ParseError> {
+        let value = f64::parse_from(option)?;
+        let (min, max) = discord_bounds(f32::MIN as i128, f32::MAX as i128);
+        if (min as f64..=max as f64).contains(&value) {
+            Ok(value as f32)
+        } else {
+            Err(ParseError::out_of_range(value, min as f64, max as f64))
+        }
+    }
+
+    fn application_command_option_type() -> ApplicationCommandOptionType {
+        ApplicationCommandOptionType::Number
+    }
+
+    fn register_option(
+        option: &mut CreateApplicationCommandOption,
+    ) -> &mut CreateApplicationCommandOption {
+        let (min, max) = discord_bounds(f32::MIN as i128, f32::MAX as i128);
+        option
+            .min_number_value(min as f64)
+            .max_number_value(max as f64)
+    }
+}
+
+/// An INTEGER option that must be between `MIN` and `MAX` (inclusive)
+///
+/// The range is sent to Discord when the command is registered, so users can only enter a value
+/// within it. It is also checked again when parsing, in case the command was registered with a
+/// different range, so the handler never sees a value outside of it.
+///
+/// Discord only accepts bounds between -2^53 and 2^53, so it is checked at compile time that the
+/// bounds are within that range and that `MIN` is not greater than `MAX`.
+/// ```
+/// # use slashies::*;
+/// # use slashies::parsable::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// /// Get some movie recommendations
+/// #[derive(Debug, Command)]
+/// #[name = "recommend"]
+/// struct RecommendCommand {
+///     /// How many movies to recommend
+///     count: Option<BoundedInt<1, 3>>,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for RecommendCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         unimplemented!()
+/// #     }
+/// # }
+///
+/// let registered = manifest::command::<RecommendCommand>();
+/// assert_eq!(registered["options"][0]["min_value"], 1);
+/// assert_eq!(registered["options"][0]["max_value"], 3);
+///
+/// let interaction = InteractionBuilder::new("recommend")
+///     .option(OptionBuilder::integer("count", 2))
+///     .build();
+/// let command = RecommendCommand::parse(&interaction).unwrap();
+/// assert_eq!(command.count.map(BoundedInt::get), Some(2));
+///
+/// let interaction = InteractionBuilder::new("recommend")
+///     .option(OptionBuilder::integer("count", 10))
+///     .build();
+/// let error = RecommendCommand::parse(&interaction).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "option `recommend count` must be between 1 and 3 (received 10)"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BoundedInt<const MIN: i64, const MAX: i64>(i64);
+
+impl<const MIN: i64, const MAX: i64> BoundedInt<MIN, MAX> {
+    const VALID_RANGE: () = {
+        assert!(MIN <= MAX, "BoundedInt's MIN must not be greater than MAX");
+        assert!(
+            -MAX_DISCORD_INTEGER <= MIN && MAX <= MAX_DISCORD_INTEGER,
+            "BoundedInt's bounds must be between -2^53 and 2^53"
+        );
+    };
+
+    /// Wrap the value, or return `None` if it is out of range
+    pub fn new(value: i64) -> Option<Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_RANGE;
+        (MIN..=MAX).contains(&value).then_some(Self(value))
+    }
+
+    /// The value
+    pub fn get(self) -> i64 {
+        self.0
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> From<BoundedInt<MIN, MAX>> for i64 {
+    fn from(value: BoundedInt<MIN, MAX>) -> Self {
+        value.0
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> ParsableCommandOption for BoundedInt<MIN, MAX> {
+    fn parse_from(
+        option: Option<&ApplicationCommandInteractionDataOption>,
+    ) -> Result<Self, ParseError> {
+        let value = i64::parse_from(option)?;
+        Self::new(value)
+            .ok_or_else(|| ParseError::out_of_range(value as f64, MIN as f64, MAX as f64))
+    }
+
+    fn application_command_option_type() -> ApplicationCommandOptionType {
+        ApplicationCommandOptionType::Integer
+    }
+
+    fn register_option(
+        option: &mut CreateApplicationCommandOption,
+    ) -> &mut CreateApplicationCommandOption {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_RANGE;
+        option.min_int_value(MIN).max_int_value(MAX)
+    }
+
+    const HAS_BOUNDS: bool = true;
+}
+
+/// A NUMBER option that must be between `MIN` and `MAX` (inclusive)
+///
+/// This works like [`BoundedInt`], but holds an [`f64`]. The bounds are integers, as floating point
+/// numbers can't be used as const generic parameters - for fractional bounds, use the `min` and
+/// `max` attributes on an [`f64`] option instead. The bounds are checked at compile time in the
+/// same way.
+/// ```
+/// # use slashies::parsable::BoundedNumber;
+/// type Rating = BoundedNumber<0, 10>;
+/// assert_eq!(Rating::new(7.5).map(Rating::get), Some(7.5));
+/// assert_eq!(Rating::new(10.5), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct BoundedNumber<const MIN: i64, const MAX: i64>(f64);
+
+impl<const MIN: i64, const MAX: i64> BoundedNumber<MIN, MAX> {
+    const VALID_RANGE: () = {
+        assert!(
+            MIN <= MAX,
+            "BoundedNumber's MIN must not be greater than MAX"
+        );
+        assert!(
+            -MAX_DISCORD_INTEGER <= MIN && MAX <= MAX_DISCORD_INTEGER,
+            "BoundedNumber's bounds must be between -2^53 and 2^53"
+        );
+    };
+
+    /// Wrap the value, or return `None` if it is out of range
+    pub fn new(value: f64) -> Option<Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_RANGE;
+        (MIN as f64..=MAX as f64)
+            .contains(&value)
+            .then_some(Self(value))
+    }
+
+    /// The value
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> From<BoundedNumber<MIN, MAX>> for f64 {
+    fn from(value: BoundedNumber<MIN, MAX>) -> Self {
+        value.0
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> ParsableCommandOption for BoundedNumber<MIN, MAX> {
+    fn parse_from(
+        option: Option<&ApplicationCommandInteractionDataOption>,
+    ) -> Result<Self, ParseError> {
+        let value = f64::parse_from(option)?;
+        Self::new(value).ok_or_else(|| ParseError::out_of_range(value, MIN as f64, MAX as f64))
+    }
+
+    fn application_command_option_type() -> ApplicationCommandOptionType {
+        ApplicationCommandOptionType::Number
+    }
+
+    fn register_option(
+        option: &mut CreateApplicationCommandOption,
+    ) -> &mut CreateApplicationCommandOption {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_RANGE;
+        option
+            .min_number_value(MIN as f64)
+            .max_number_value(MAX as f64)
+    }
+
+    const HAS_BOUNDS: bool = true;
+}
+
+/// An input for the ATTACHMENT Discord type
+///
+/// ```
+/// # use slashies::*;
+/// # use slashies::parsable::*;
+/// # use slashies_macros::*;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::prelude::application_command::*;
+/// /// Upload an image to the gallery
+/// #[derive(Debug, Command)]
+/// #[name = "upload"]
+/// struct UploadCommand {
+///     /// A caption for the image
+///     caption: Option<String>,
+///
+///     /// The image to upload
+///     image: AttachmentInput,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for UploadCommand {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         unimplemented!()
+/// #     }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AttachmentInput {
+    /// The uploaded attachment
+    pub attachment: Attachment,
+}
+
+impl ParsableCommandOption for AttachmentInput {
+    fn parse_from(
+        option: Option<&ApplicationCommandInteractionDataOption>,
+    ) -> Result<Self, ParseError> {
+        match resolved_value::<Self>(option)? {
+            ApplicationCommandInteractionDataOptionValue::Attachment(a) => {
+                Ok(AttachmentInput { attachment: a })
+            }
+            value => Err(ParseError::invalid_option(
+                Self::application_command_option_type(),
+                &value,
+            )),
+        }
+    }
+
+    fn application_command_option_type() -> ApplicationCommandOptionType {
+        ApplicationCommandOptionType::Attachment
+    }
+}
+
 impl<T: ParsableCommandOption> ParsableCommandOption for Option<T> {
     fn parse_from(
         option: Option<&ApplicationCommandInteractionDataOption>,
@@ -249,4 +716,316 @@ impl<T: ParsableCommandOption> ParsableCommandOption for Option<T> {
     fn is_required() -> bool {
         false
     }
+
+    fn register_option(
+        option: &mut CreateApplicationCommandOption,
+    ) -> &mut CreateApplicationCommandOption {
+        T::register_option(option)
+    }
+
+    const HAS_BOUNDS: bool = T::HAS_BOUNDS;
+}
+
+/// This trait contains the functions needed to parse the value of a text input in a
+/// [`Modal`](crate::Modal)
+///
+/// For a text input the user can leave empty, use [`Option<T>`] - empty inputs are parsed as
+/// `None`.
+///
+/// The following types are implemented out of the box:
+///
+/// | Rust type  |
+/// |------------|
+/// | [`String`] |
+pub trait ParsableTextInput: Sized {
+    /// Try to parse this from the value the user submitted for the text input with the given custom
+    /// ID. The value might be missing, hence the optional input - if this is a non-optional type we
+    /// would normally return a [`ParseError::MissingInput`] in this case.
+    fn parse_input(custom_id: &str, value: Option<&str>) -> Result<Self, ParseError>;
+
+    /// Whether the user must fill in the text input. Defaults to `true`.
+    fn is_required() -> bool {
+        true
+    }
+}
+
+/// Find the value submitted for the text input with the given custom ID in a modal
+pub fn text_input_value<'a>(
+    interaction: &'a ModalSubmitInteraction,
+    custom_id: &str,
+) -> Option<&'a str> {
+    interaction
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == custom_id => {
+                Some(input.value.as_str())
+            }
+            _ => None,
+        })
+}
+
+impl ParsableTextInput for String {
+    fn parse_input(custom_id: &str, value: Option<&str>) -> Result<Self, ParseError> {
+        value
+            .map(str::to_owned)
+            .ok_or_else(|| ParseError::MissingInput {
+                custom_id: custom_id.to_owned(),
+            })
+    }
+}
+
+impl<T: ParsableTextInput> ParsableTextInput for Option<T> {
+    fn parse_input(custom_id: &str, value: Option<&str>) -> Result<Self, ParseError> {
+        match value {
+            Some(value) if !value.is_empty() => Ok(Some(T::parse_input(custom_id, Some(value))?)),
+            _ => Ok(None),
+        }
+    }
+
+    fn is_required() -> bool {
+        false
+    }
+}
+
+/// This trait contains the functions needed to store a value in the custom ID of a message
+/// component (e.g. in the fields of a [`Buttons`](crate::Buttons) variant), and to read it back
+/// when the component is used
+///
+/// Values are separated by `:` in the custom ID, so implementations must not produce a `:` in
+/// their encoded form. Discord limits custom IDs to [`MAX_CUSTOM_ID_LENGTH`] characters, so keep
+/// the encoded values short.
+///
+/// The following types are implemented out of the box:
+///
+/// | Rust type                                                                       |
+/// |---------------------------------------------------------------------------------|
+/// | [`String`] (with `%` and `:` percent-encoded)                                   |
+/// | [`bool`]                                                                        |
+/// | [`i8`], [`i16`], [`i32`], [`i64`], [`u8`], [`u16`], [`u32`], [`u64`], [`usize`] |
+/// | [`UserId`], [`ChannelId`], [`GuildId`], [`RoleId`], [`MessageId`]               |
+///
+/// ```
+/// # use slashies::parsable::CustomIdField;
+/// let encoded = "12:30 at 50%".to_owned().to_custom_id();
+/// assert_eq!(encoded, "12%3A30 at 50%25");
+/// assert_eq!(String::parse_custom_id(&encoded).as_deref(), Some("12:30 at 50%"));
+/// ```
+pub trait CustomIdField: Sized {
+    /// Encode the value for use in a custom ID
+    fn to_custom_id(&self) -> String;
+
+    /// Decode a value that was encoded by [`CustomIdField::to_custom_id`], or `None` if it is
+    /// malformed
+    fn parse_custom_id(value: &str) -> Option<Self>;
+
+    /// The maximum length of the encoded value, if there is one, which lets the length of a
+    /// [`CustomIdCodec`](crate::CustomIdCodec) be checked at compile time
+    const MAX_LENGTH: Option<usize> = None;
+}
+
+impl CustomIdField for String {
+    fn to_custom_id(&self) -> String {
+        self.replace('%', "%25").replace(':', "%3A")
+    }
+
+    fn parse_custom_id(value: &str) -> Option<Self> {
+        let mut decoded = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(index) = rest.find('%') {
+            decoded.push_str(&rest[..index]);
+            match rest.get(index..index + 3)? {
+                "%25" => decoded.push('%'),
+                "%3A" => decoded.push(':'),
+                _ => return None,
+            }
+            rest = &rest[index + 3..];
+        }
+        decoded.push_str(rest);
+        Some(decoded)
+    }
+}
+
+macro_rules! impl_custom_id_field_via_from_str {
+    ($($field_type:ty => $max_length:expr),*) => {
+        $(
+            impl CustomIdField for $field_type {
+                fn to_custom_id(&self) -> String {
+                    self.to_string()
+                }
+
+                fn parse_custom_id(value: &str) -> Option<Self> {
+                    value.parse().ok()
+                }
+
+                const MAX_LENGTH: Option<usize> = Some($max_length);
+            }
+        )*
+    };
+}
+
+// The maximum lengths are those of "false" and the minimum/maximum values of each type
+impl_custom_id_field_via_from_str!(
+    bool => 5,
+    i8 => 4,
+    i16 => 6,
+    i32 => 11,
+    i64 => 20,
+    u8 => 3,
+    u16 => 5,
+    u32 => 10,
+    u64 => 20,
+    usize => 20
+);
+
+macro_rules! impl_custom_id_field_for_id {
+    ($($id_type:ident),*) => {
+        $(
+            impl CustomIdField for $id_type {
+                fn to_custom_id(&self) -> String {
+                    self.0.to_string()
+                }
+
+                fn parse_custom_id(value: &str) -> Option<Self> {
+                    value.parse().ok().map($id_type)
+                }
+
+                const MAX_LENGTH: Option<usize> = u64::MAX_LENGTH;
+            }
+        )*
+    };
+}
+
+impl_custom_id_field_for_id!(UserId, ChannelId, GuildId, RoleId, MessageId);
+
+/// Discord's limit on the length of a message component's custom ID
+pub const MAX_CUSTOM_ID_LENGTH: usize = 100;
+
+/// The error returned when state encoded by a [`CustomIdCodec`](crate::CustomIdCodec) doesn't fit
+/// in a custom ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomIdTooLong {
+    /// The encoded custom ID
+    pub custom_id: String,
+    /// The length of the encoded custom ID in characters
+    pub length: usize,
+}
+
+impl fmt::Display for CustomIdTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "custom ID `{}` is {} characters long, but can be at most {}",
+            self.custom_id, self.length, MAX_CUSTOM_ID_LENGTH
+        )
+    }
+}
+
+impl Error for CustomIdTooLong {}
+
+/// Return the custom ID if it fits within [`MAX_CUSTOM_ID_LENGTH`], or an error otherwise
+pub(crate) fn check_custom_id_length(custom_id: String) -> Result<String, CustomIdTooLong> {
+    match custom_id.chars().count() {
+        length if length > MAX_CUSTOM_ID_LENGTH => Err(CustomIdTooLong { custom_id, length }),
+        _ => Ok(custom_id),
+    }
+}
+
+impl From<CustomIdTooLong> for InvocationError {
+    fn from(error: CustomIdTooLong) -> Self {
+        Self::new(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::{InteractionBuilder, OptionBuilder};
+
+    /// Parse a single option as the given type
+    fn parse<T: ParsableCommandOption>(option: OptionBuilder) -> Result<T, ParseError> {
+        let interaction = InteractionBuilder::new("test").option(option).build();
+        T::parse_from(interaction.data.options.first())
+    }
+
+    fn register<T: ParsableCommandOption>() -> CreateApplicationCommandOption {
+        let mut option = CreateApplicationCommandOption::default();
+        T::register_option(&mut option);
+        option
+    }
+
+    fn assert_out_of_range<T: fmt::Debug>(result: Result<T, ParseError>, expected: (f64, f64)) {
+        match result {
+            Err(ParseError::OutOfRange { min, max, .. }) => assert_eq!((min, max), expected),
+            result => panic!("Expected an out of range error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn large_integers_are_capped_at_what_discord_accepts() {
+        let limit = MAX_DISCORD_INTEGER as f64;
+        for option in [register::<u64>(), register::<usize>()] {
+            assert_eq!(option.0["min_value"], 0);
+            assert_eq!(option.0["max_value"], MAX_DISCORD_INTEGER);
+        }
+        assert_eq!(
+            parse::<u64>(OptionBuilder::integer("n", MAX_DISCORD_INTEGER)).unwrap(),
+            1 << 53
+        );
+        assert_out_of_range(
+            parse::<u64>(OptionBuilder::integer("n", MAX_DISCORD_INTEGER + 1)),
+            (0.0, limit),
+        );
+        assert_out_of_range(
+            parse::<usize>(OptionBuilder::integer("n", -1)),
+            (0.0, limit),
+        );
+    }
+
+    #[test]
+    fn non_zero_integers_reject_zero() {
+        assert_eq!(register::<NonZeroU32>().0["min_value"], 1);
+        assert_eq!(
+            parse::<NonZeroU32>(OptionBuilder::integer("n", 5)).unwrap(),
+            NonZeroU32::new(5).unwrap()
+        );
+        assert_out_of_range(
+            parse::<NonZeroU32>(OptionBuilder::integer("n", 0)),
+            (1.0, u32::MAX as f64),
+        );
+    }
+
+    #[test]
+    fn f32_rejects_values_it_cant_hold() {
+        let limit = MAX_DISCORD_INTEGER as f64;
+        assert_eq!(parse::<f32>(OptionBuilder::number("n", 2.5)).unwrap(), 2.5);
+        assert_out_of_range(
+            parse::<f32>(OptionBuilder::number("n", 1e300)),
+            (-limit, limit),
+        );
+    }
+
+    #[test]
+    fn optional_integers_are_registered_with_their_bounds() {
+        let option = register::<Option<u32>>();
+        assert_eq!(option.0["min_value"], 0);
+        assert_eq!(option.0["max_value"], u32::MAX);
+        assert!(!<Option<u32>>::is_required());
+        assert_eq!(
+            parse::<Option<u32>>(OptionBuilder::integer("n", 7)).unwrap(),
+            Some(7)
+        );
+        assert_eq!(<Option<u32>>::parse_from(None).unwrap(), None);
+    }
+
+    #[test]
+    fn invalid_bounds_fail_to_compile() {
+        let t = trybuild::TestCases::new();
+        // The bounds are checked when the types are used, which `cargo check` doesn't catch, but
+        // having a passing case makes trybuild build the failing ones too
+        t.pass("tests/parsable/valid_bounds.rs");
+        t.compile_fail("tests/parsable/e*.rs");
+    }
 }
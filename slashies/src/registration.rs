@@ -0,0 +1,385 @@
+use serenity::{
+    builder::CreateApplicationCommand,
+    http::{request::RequestBuilder, routing::RouteInfo, Http, HttpError},
+    json::{hashmap_to_json_map, json, JsonMap, Value},
+    model::id::{CommandId, GuildId},
+};
+
+/// What [`sync_commands`] did to a single command
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistrationAction {
+    /// The command was not registered yet, so it was created
+    Created {
+        /// The name of the command
+        name: String,
+        /// The ID Discord assigned to the new command
+        id: CommandId,
+    },
+    /// The registered command was different, so it was edited
+    Edited {
+        /// The name of the command
+        name: String,
+        /// The ID of the command
+        id: CommandId,
+    },
+    /// The registered command was already up to date, so it was left alone
+    Unchanged {
+        /// The name of the command
+        name: String,
+        /// The ID of the command
+        id: CommandId,
+    },
+    /// The registered command is no longer provided, so it was deleted
+    Deleted {
+        /// The name of the command
+        name: String,
+        /// The ID the command had
+        id: CommandId,
+    },
+}
+
+/// A report of the actions [`sync_commands`] took, in the order they were taken
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegistrationReport {
+    /// The action taken for each command
+    pub actions: Vec<RegistrationAction>,
+}
+
+impl RegistrationReport {
+    /// The names of the commands that were created
+    pub fn created(&self) -> Vec<&str> {
+        self.names(|action| matches!(action, RegistrationAction::Created { .. }))
+    }
+
+    /// The names of the commands that were edited
+    pub fn edited(&self) -> Vec<&str> {
+        self.names(|action| matches!(action, RegistrationAction::Edited { .. }))
+    }
+
+    /// The names of the commands that were already up to date
+    pub fn unchanged(&self) -> Vec<&str> {
+        self.names(|action| matches!(action, RegistrationAction::Unchanged { .. }))
+    }
+
+    /// The names of the commands that were deleted
+    pub fn deleted(&self) -> Vec<&str> {
+        self.names(|action| matches!(action, RegistrationAction::Deleted { .. }))
+    }
+
+    /// Whether any commands were created, edited or deleted
+    pub fn has_changes(&self) -> bool {
+        self.actions
+            .iter()
+            .any(|action| !matches!(action, RegistrationAction::Unchanged { .. }))
+    }
+
+    fn names(&self, filter: impl Fn(&RegistrationAction) -> bool) -> Vec<&str> {
+        self.actions
+            .iter()
+            .filter(|action| filter(action))
+            .map(|action| match action {
+                RegistrationAction::Created { name, .. }
+                | RegistrationAction::Edited { name, .. }
+                | RegistrationAction::Unchanged { name, .. }
+                | RegistrationAction::Deleted { name, .. } => name.as_str(),
+            })
+            .collect()
+    }
+}
+
+/// Bring the commands registered with Discord (globally, or in a guild) in line with the given
+/// commands, only sending requests for the commands that actually changed.
+///
+/// Unlike a bulk overwrite, this keeps the IDs of existing commands and doesn't use up rate limits
+/// when nothing has changed. The registered commands are fetched and compared structurally with
+/// the given ones (ignoring fields Discord fills in, such as IDs and versions), then:
+/// - commands that aren't registered yet are created
+/// - commands that are registered but differ are edited
+/// - registered commands that aren't in the list are deleted
+///
+/// Commands are matched by their name and type. Usually you'll want to use the
+/// [`sync_commands!`](crate::sync_commands!) macro rather than calling this directly.
+pub async fn sync_commands(
+    http: impl AsRef<Http>,
+    guild_id: Option<GuildId>,
+    commands: &[CreateApplicationCommand],
+) -> Result<RegistrationReport, serenity::Error> {
+    let http = http.as_ref();
+    let application_id = http
+        .application_id()
+        .ok_or(HttpError::ApplicationIdMissing)?;
+    let route = match guild_id {
+        Some(guild_id) => RouteInfo::GetGuildApplicationCommands {
+            application_id,
+            guild_id: guild_id.0,
+        },
+        None => RouteInfo::GetGlobalApplicationCommands { application_id },
+    };
+    // Fetch the raw JSON rather than serenity's model, which doesn't keep every field (such as
+    // whether an option uses autocomplete)
+    let mut existing: Vec<Value> = http.fire(RequestBuilder::new(route).build()).await?;
+
+    let mut report = RegistrationReport::default();
+    for command in commands {
+        let desired = Value::Object(hashmap_to_json_map(command.0.clone()));
+        let name = desired["name"].as_str().unwrap_or_default().to_owned();
+        let position = existing.iter().position(|registered| {
+            registered["name"] == desired["name"]
+                && normalize_command(registered)["type"] == normalize_command(&desired)["type"]
+        });
+        let action = match position.map(|position| existing.remove(position)) {
+            None => {
+                let created = match guild_id {
+                    Some(guild_id) => {
+                        http.create_guild_application_command(guild_id.0, &desired)
+                            .await?
+                    }
+                    None => http.create_global_application_command(&desired).await?,
+                };
+                RegistrationAction::Created {
+                    name,
+                    id: created.id,
+                }
+            }
+            Some(registered) => {
+                let id = command_id(&registered);
+                if normalize_command(&registered) == normalize_command(&desired) {
+                    RegistrationAction::Unchanged { name, id }
+                } else {
+                    match guild_id {
+                        Some(guild_id) => {
+                            http.edit_guild_application_command(guild_id.0, id.0, &desired)
+                                .await?
+                        }
+                        None => http.edit_global_application_command(id.0, &desired).await?,
+                    };
+                    RegistrationAction::Edited { name, id }
+                }
+            }
+        };
+        report.actions.push(action);
+    }
+
+    for registered in existing {
+        let id = command_id(&registered);
+        match guild_id {
+            Some(guild_id) => {
+                http.delete_guild_application_command(guild_id.0, id.0)
+                    .await?
+            }
+            None => http.delete_global_application_command(id.0).await?,
+        }
+        report.actions.push(RegistrationAction::Deleted {
+            name: registered["name"].as_str().unwrap_or_default().to_owned(),
+            id,
+        });
+    }
+    Ok(report)
+}
+
+fn command_id(command: &Value) -> CommandId {
+    CommandId(
+        command["id"]
+            .as_str()
+            .and_then(|id| id.parse().ok())
+            .unwrap_or_default(),
+    )
+}
+
+/// Reduce a command to the fields that can be set when registering it, filling in the defaults
+/// Discord uses for missing fields, so that two commands can be compared structurally
+fn normalize_command(command: &Value) -> Value {
+    let mut normalized = JsonMap::new();
+    normalized.insert("type".to_owned(), or_default(&command["type"], json!(1)));
+    normalized.insert("name".to_owned(), command["name"].clone());
+    normalized.insert(
+        "description".to_owned(),
+        or_default(&command["description"], json!("")),
+    );
+    normalized.insert("options".to_owned(), normalize_options(&command["options"]));
+    normalized.insert(
+        "default_member_permissions".to_owned(),
+        command["default_member_permissions"].clone(),
+    );
+    normalized.insert(
+        "dm_permission".to_owned(),
+        or_default(&command["dm_permission"], json!(true)),
+    );
+    normalize_localizations(command, &mut normalized);
+    Value::Object(normalized)
+}
+
+fn normalize_options(options: &Value) -> Value {
+    let options = options.as_array().cloned().unwrap_or_default();
+    Value::Array(
+        options
+            .iter()
+            .map(|option| {
+                let mut normalized = JsonMap::new();
+                for key in ["type", "name", "description"] {
+                    normalized.insert(key.to_owned(), option[key].clone());
+                }
+                normalized.insert(
+                    "required".to_owned(),
+                    or_default(&option["required"], json!(false)),
+                );
+                normalized.insert(
+                    "autocomplete".to_owned(),
+                    or_default(&option["autocomplete"], json!(false)),
+                );
+                normalized.insert("choices".to_owned(), normalize_choices(&option["choices"]));
+                normalized.insert("options".to_owned(), normalize_options(&option["options"]));
+                normalized.insert(
+                    "channel_types".to_owned(),
+                    or_default(&option["channel_types"], json!([])),
+                );
+                for key in ["min_value", "max_value"] {
+                    normalized.insert(key.to_owned(), normalize_number(&option[key]));
+                }
+                normalize_localizations(option, &mut normalized);
+                Value::Object(normalized)
+            })
+            .collect(),
+    )
+}
+
+fn normalize_choices(choices: &Value) -> Value {
+    let choices = choices.as_array().cloned().unwrap_or_default();
+    Value::Array(
+        choices
+            .iter()
+            .map(|choice| {
+                let mut normalized = JsonMap::new();
+                normalized.insert("name".to_owned(), choice["name"].clone());
+                let value = match &choice["value"] {
+                    Value::Number(_) => normalize_number(&choice["value"]),
+                    value => value.clone(),
+                };
+                normalized.insert("value".to_owned(), value);
+                if let Some(localizations) = non_empty(&choice["name_localizations"]) {
+                    normalized.insert("name_localizations".to_owned(), localizations);
+                }
+                Value::Object(normalized)
+            })
+            .collect(),
+    )
+}
+
+fn normalize_localizations(source: &Value, normalized: &mut JsonMap) {
+    for key in ["name_localizations", "description_localizations"] {
+        if let Some(localizations) = non_empty(&source[key]) {
+            normalized.insert(key.to_owned(), localizations);
+        }
+    }
+}
+
+/// Numbers may come back from Discord as floats even if they were sent as integers
+fn normalize_number(number: &Value) -> Value {
+    match number.as_f64() {
+        Some(number) => json!(number),
+        None => Value::Null,
+    }
+}
+
+fn non_empty(value: &Value) -> Option<Value> {
+    match value {
+        Value::Object(map) if !map.is_empty() => Some(value.clone()),
+        _ => None,
+    }
+}
+
+fn or_default(value: &Value, default: Value) -> Value {
+    match value {
+        Value::Null => default,
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serenity::model::interactions::application_command::ApplicationCommandOptionType;
+    use slashies_test::FakeDiscord;
+
+    fn greet_command(description: &str) -> CreateApplicationCommand {
+        let mut command = CreateApplicationCommand::default();
+        command
+            .name("greet")
+            .description(description)
+            .create_option(|option| {
+                option
+                    .kind(ApplicationCommandOptionType::Integer)
+                    .name("times")
+                    .description("How many times to greet")
+                    .required(false)
+                    .min_int_value(1)
+                    .add_int_choice("Once", 1)
+            });
+        command
+    }
+
+    #[test]
+    fn registered_commands_match_regardless_of_discord_defaults() {
+        let desired = Value::Object(hashmap_to_json_map(greet_command("Greet a user").0));
+        // As Discord would send it back: with IDs, without default values, with floats
+        let registered = json!({
+            "id": "10",
+            "application_id": "1",
+            "guild_id": "1",
+            "version": "3",
+            "type": 1,
+            "name": "greet",
+            "description": "Greet a user",
+            "default_member_permissions": null,
+            "options": [{
+                "type": 4,
+                "name": "times",
+                "description": "How many times to greet",
+                "min_value": 1.0,
+                "choices": [{ "name": "Once", "value": 1 }],
+            }],
+        });
+        assert_eq!(normalize_command(&registered), normalize_command(&desired));
+
+        let mut changed = registered;
+        changed["options"][0]["autocomplete"] = json!(true);
+        assert_ne!(normalize_command(&changed), normalize_command(&desired));
+    }
+
+    #[tokio::test]
+    async fn changed_commands_are_edited_in_place() {
+        let discord = FakeDiscord::start().await;
+        let http = discord.http();
+
+        let report = sync_commands(&http, None, &[greet_command("Greet a user")])
+            .await
+            .unwrap();
+        let id = match report.actions[..] {
+            [RegistrationAction::Created { id, .. }] => id,
+            _ => panic!("Unexpected report {:?}", report),
+        };
+
+        let report = sync_commands(&http, None, &[greet_command("Say hello to a user")])
+            .await
+            .unwrap();
+        assert_eq!(
+            report.actions,
+            vec![RegistrationAction::Edited {
+                name: "greet".to_owned(),
+                id
+            }]
+        );
+        assert_eq!(
+            discord.commands(None)[0]["description"],
+            "Say hello to a user"
+        );
+        assert_eq!(
+            discord
+                .requests()
+                .iter()
+                .filter(|request| request.method == "PUT")
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use crate::{
+    framework::ComponentHandler, InvocationError, MessageComponentInteractionHandler, ParseError,
+};
+use serenity::{
+    async_trait,
+    client::Context,
+    http::Http,
+    json::{json, prelude::to_value, Value},
+    model::{
+        channel::Message, id::MessageId,
+        interactions::message_component::MessageComponentInteraction,
+    },
+    prelude::TypeMapKey,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tokio::{
+    task::JoinHandle,
+    time::{self, Instant},
+};
+
+/// A registry of the handlers for the components on each message
+///
+/// When a message with components is sent, [insert](ComponentSessions::insert) a
+/// [`MessageComponentInteractionHandler`] holding the state that its components act on. Interactions
+/// with those components are then routed to the handler by the message's ID, along with the
+/// message so that it can be edited in response. If the components aren't used for the session's
+/// time to live, the session ends and the message's components are disabled.
+///
+/// The registry is cheap to clone, so the same sessions can be handed to
+/// [`Framework::on_component`](crate::framework::Framework::on_component) and inserted into the
+/// context's data under [`ComponentSessionsKey`] for commands to add to:
+/// ```
+/// # use slashies::*;
+/// # use slashies::framework::ComponentHandler;
+/// # use slashies::sessions::*;
+/// # use slashies::testing::*;
+/// # use slashies_test::FakeDiscord;
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::channel::Message;
+/// # use serenity::model::interactions::InteractionResponseType;
+/// # use serenity::model::interactions::message_component::MessageComponentInteraction;
+/// # use std::time::Duration;
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// #[async_trait]
+/// impl MessageComponentInteractionHandler for Counter {
+///     async fn invoke(
+///         &mut self,
+///         ctx: &Context,
+///         interaction: &MessageComponentInteraction,
+///         _original_message: &mut Message,
+///     ) {
+///         self.count += 1;
+///         let content = format!("Clicked {} times", self.count);
+///         interaction
+///             .create_interaction_response(&ctx.http, |response| {
+///                 response
+///                     .kind(InteractionResponseType::UpdateMessage)
+///                     .interaction_response_data(|message| message.content(content))
+///             })
+///             .await
+///             .unwrap();
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// # let discord = FakeDiscord::start().await;
+/// # let ctx = discord.context();
+/// let sessions = ComponentSessions::new(Duration::from_secs(15 * 60));
+/// ctx.data
+///     .write()
+///     .await
+///     .insert::<ComponentSessionsKey>(sessions.clone());
+///
+/// // Once the message with the button has been sent...
+/// # let message = ComponentInteractionBuilder::new("click").build().message;
+/// sessions.insert(&ctx, message.clone(), Counter { count: 0 });
+///
+/// // ...clicks on the button are routed to its handler
+/// let interaction = ComponentInteractionBuilder::new("click")
+///     .message_id(message.id)
+///     .build();
+/// sessions.handle_component(&ctx, &interaction).await.unwrap();
+/// sessions.handle_component(&ctx, &interaction).await.unwrap();
+/// assert_eq!(
+///     discord.interaction_responses()[1]["data"]["content"],
+///     "Clicked 2 times"
+/// );
+/// # }
+/// ```
+/// The components are disabled by editing the message in its channel, which isn't possible for
+/// ephemeral messages - their components are left as they are when the session ends.
+#[derive(Clone)]
+pub struct ComponentSessions {
+    ttl: Duration,
+    sessions: Arc<Mutex<SessionMap>>,
+}
+
+type SessionMap = HashMap<MessageId, Arc<tokio::sync::Mutex<Session>>>;
+
+struct Session {
+    handler: Box<dyn MessageComponentInteractionHandler + Send>,
+    message: Message,
+    expires_at: Instant,
+}
+
+impl ComponentSessions {
+    /// Create a registry whose sessions end once their components haven't been used for the given
+    /// time to live
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            sessions: Arc::default(),
+        }
+    }
+
+    /// Route interactions with the components on the message to the handler, replacing any
+    /// session the message already had
+    ///
+    /// # Panics
+    /// Panics if called outside of a tokio runtime, as a task is spawned to end the session
+    pub fn insert(
+        &self,
+        ctx: &Context,
+        message: Message,
+        handler: impl MessageComponentInteractionHandler + Send + 'static,
+    ) {
+        self.start_session(ctx, message, handler);
+    }
+
+    /// Insert the session, returning the task that ends it
+    fn start_session(
+        &self,
+        ctx: &Context,
+        message: Message,
+        handler: impl MessageComponentInteractionHandler + Send + 'static,
+    ) -> JoinHandle<()> {
+        let message_id = message.id;
+        let session = Arc::new(tokio::sync::Mutex::new(Session {
+            handler: Box::new(handler),
+            message,
+            expires_at: Instant::now() + self.ttl,
+        }));
+        self.lock().insert(message_id, session.clone());
+        tokio::spawn(self.clone().expire(ctx.http.clone(), message_id, session))
+    }
+
+    /// End the message's session without disabling its components, returning whether it had one
+    pub fn remove(&self, message_id: MessageId) -> bool {
+        self.lock().remove(&message_id).is_some()
+    }
+
+    /// Whether the message has a session
+    pub fn contains(&self, message_id: MessageId) -> bool {
+        self.lock().contains_key(&message_id)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SessionMap> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Wait until the session has gone unused for the time to live, then end it and disable the
+    /// message's components
+    async fn expire(
+        self,
+        http: Arc<Http>,
+        message_id: MessageId,
+        session: Arc<tokio::sync::Mutex<Session>>,
+    ) {
+        loop {
+            let expires_at = session.lock().await.expires_at;
+            time::sleep_until(expires_at).await;
+            let guard = session.lock().await;
+            if guard.expires_at > Instant::now() {
+                // The components were used while we were asleep
+                continue;
+            }
+            {
+                let mut sessions = self.lock();
+                match sessions.get(&message_id) {
+                    Some(current) if Arc::ptr_eq(current, &session) => {
+                        sessions.remove(&message_id);
+                    }
+                    // The session was removed or replaced, so it's no longer ours to end
+                    _ => return,
+                }
+            }
+            let edit = json!({ "components": disabled_components(&guard.message) });
+            let result = http
+                .edit_message(guard.message.channel_id.0, message_id.0, &edit)
+                .await;
+            if let Err(error) = result {
+                tracing::error!(
+                    "Failed to disable the components of message {}: {}",
+                    message_id,
+                    error
+                );
+            }
+            return;
+        }
+    }
+}
+
+/// The message's action rows, with every component disabled
+fn disabled_components(message: &Message) -> Vec<Value> {
+    message
+        .components
+        .iter()
+        .filter_map(|row| to_value(row).ok())
+        .map(|mut row| {
+            if let Some(components) = row["components"].as_array_mut() {
+                for component in components {
+                    component["disabled"] = Value::Bool(true);
+                }
+            }
+            row
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ComponentHandler for ComponentSessions {
+    async fn handle_component(
+        &self,
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+    ) -> Result<(), InvocationError> {
+        let unknown = || ParseError::UnknownComponent {
+            custom_id: interaction.data.custom_id.clone(),
+        };
+        let session = self
+            .lock()
+            .get(&interaction.message.id)
+            .cloned()
+            .ok_or_else(unknown)?;
+        let mut guard = session.lock().await;
+        // The session may have ended or been replaced while we were waiting for it
+        match self.lock().get(&interaction.message.id) {
+            Some(current) if Arc::ptr_eq(current, &session) => (),
+            _ => return Err(unknown().into()),
+        }
+        let Session {
+            handler,
+            message,
+            expires_at,
+        } = &mut *guard;
+        *expires_at = Instant::now() + self.ttl;
+        *message = interaction.message.clone();
+        handler.invoke(ctx, interaction, message).await;
+        Ok(())
+    }
+}
+
+/// The key for the [`ComponentSessions`] in the context's data
+pub struct ComponentSessionsKey;
+
+impl TypeMapKey for ComponentSessionsKey {
+    type Value = ComponentSessions;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::ComponentInteractionBuilder;
+    use slashies_test::FakeDiscord;
+
+    struct Clicks(Arc<Mutex<u32>>);
+
+    #[async_trait]
+    impl MessageComponentInteractionHandler for Clicks {
+        async fn invoke(
+            &mut self,
+            _ctx: &Context,
+            _interaction: &MessageComponentInteraction,
+            _original_message: &mut Message,
+        ) {
+            *self.0.lock().unwrap() += 1;
+        }
+    }
+
+    #[tokio::test]
+    async fn sessions_expire_and_disable_their_components() {
+        let discord = FakeDiscord::start().await;
+        let ctx = discord.context();
+        let sessions = ComponentSessions::new(Duration::from_millis(200));
+        let clicks = Arc::new(Mutex::new(0));
+
+        let mut payload = ComponentInteractionBuilder::new("click")
+            .message_id(MessageId(5))
+            .to_json();
+        payload["message"]["components"] = json!([{
+            "type": 1,
+            "components": [{ "type": 2, "style": 1, "label": "Click", "custom_id": "click" }],
+        }]);
+        let interaction: MessageComponentInteraction =
+            serenity::json::prelude::from_value(payload).unwrap();
+
+        // Only time passing explicitly counts towards the time to live
+        time::pause();
+        let expiry =
+            sessions.start_session(&ctx, interaction.message.clone(), Clicks(clicks.clone()));
+
+        // Using the components keeps the session alive
+        for _ in 0..3 {
+            time::advance(Duration::from_millis(100)).await;
+            sessions.handle_component(&ctx, &interaction).await.unwrap();
+        }
+        assert_eq!(*clicks.lock().unwrap(), 3);
+        assert!(sessions.contains(MessageId(5)));
+
+        time::advance(Duration::from_millis(200)).await;
+        // Let the request to disable the components go through in real time
+        time::resume();
+        expiry.await.unwrap();
+        assert!(!sessions.contains(MessageId(5)));
+        let error = sessions
+            .handle_component(&ctx, &interaction)
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "unknown component `click`");
+        let edits = discord.message_edits();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].0, MessageId(5));
+        assert_eq!(
+            edits[0].1["components"][0]["components"][0]["disabled"],
+            true
+        );
+    }
+
+    #[tokio::test]
+    async fn replaced_sessions_are_not_ended_early() {
+        let discord = FakeDiscord::start().await;
+        let ctx = discord.context();
+        let sessions = ComponentSessions::new(Duration::from_millis(200));
+        let interaction = ComponentInteractionBuilder::new("click")
+            .message_id(MessageId(5))
+            .build();
+
+        time::pause();
+        let first = Arc::new(Mutex::new(0));
+        let first_expiry =
+            sessions.start_session(&ctx, interaction.message.clone(), Clicks(first.clone()));
+        time::advance(Duration::from_millis(100)).await;
+        let second = Arc::new(Mutex::new(0));
+        sessions.start_session(&ctx, interaction.message.clone(), Clicks(second.clone()));
+
+        // The first session's time to live runs out, but it no longer owns the message
+        time::advance(Duration::from_millis(150)).await;
+        first_expiry.await.unwrap();
+        sessions.handle_component(&ctx, &interaction).await.unwrap();
+        assert_eq!(*first.lock().unwrap(), 0);
+        assert_eq!(*second.lock().unwrap(), 1);
+        assert!(discord.message_edits().is_empty());
+    }
+}
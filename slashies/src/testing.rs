@@ -0,0 +1,690 @@
+use serde_json::{json, Map, Value};
+use serenity::model::{
+    channel::ChannelType,
+    id::{AttachmentId, ChannelId, GuildId, MessageId, RoleId, UserId},
+    interactions::{
+        application_command::{
+            ApplicationCommandInteraction, ApplicationCommandOptionType, ApplicationCommandType,
+        },
+        autocomplete::AutocompleteInteraction,
+        message_component::{ComponentType, MessageComponentInteraction},
+        modal::ModalSubmitInteraction,
+        InteractionType,
+    },
+};
+
+/// Builds an [`ApplicationCommandInteraction`] offline, so that commands can be parsed in tests
+/// without a connection to Discord.
+///
+/// The interaction is built as the JSON payload Discord would send and then deserialized by
+/// serenity, so option values are resolved in the same way as they would be for a live
+/// interaction. By default, the interaction is invoked from guild `1` in channel `1` by user `1`.
+///
+/// ```
+/// # use serenity::async_trait;
+/// # use serenity::prelude::*;
+/// # use serenity::model::id::{RoleId, UserId};
+/// # use serenity::model::prelude::application_command::*;
+/// # use slashies::*;
+/// # use slashies::parsable::*;
+/// # use slashies::testing::*;
+/// # use slashies_macros::*;
+/// #[derive(Debug, SubCommand)]
+/// struct GrantRole {
+///     /// The user to grant the role to
+///     user: UserInput,
+///     /// The role to grant
+///     role: serenity::model::guild::Role,
+///     /// Why the role is being granted
+///     reason: Option<String>,
+/// }
+/// # #[async_trait]
+/// # impl ApplicationCommandInteractionHandler for GrantRole {
+/// #     async fn invoke(
+/// #         &self,
+/// #         ctx: &Context,
+/// #         command: &ApplicationCommandInteraction,
+/// #     ) -> Result<(), InvocationError> {
+/// #         unimplemented!()
+/// #     }
+/// # }
+///
+/// /// Manage roles
+/// #[derive(Debug, Command, ApplicationCommandInteractionHandler)]
+/// #[name = "roles"]
+/// enum RolesCommand {
+///     /// Grant a role to a user
+///     #[name = "grant"]
+///     Grant(GrantRole),
+/// }
+///
+/// let interaction = InteractionBuilder::new("roles")
+///     .resolved_user(UserId(5), "ferris")
+///     .resolved_member(UserId(5), Some("crab"))
+///     .resolved_role(RoleId(7), "moderator")
+///     .option(
+///         OptionBuilder::sub_command("grant")
+///             .option(OptionBuilder::user("user", UserId(5)))
+///             .option(OptionBuilder::role("role", RoleId(7))),
+///     )
+///     .build();
+///
+/// match RolesCommand::parse(&interaction).expect("Failed to parse command") {
+///     RolesCommand::Grant(grant) => {
+///         assert_eq!(grant.user.user.name, "ferris");
+///         assert_eq!(grant.user.member.unwrap().nick.as_deref(), Some("crab"));
+///         assert_eq!(grant.role.name, "moderator");
+///         assert!(grant.reason.is_none());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InteractionBuilder {
+    name: String,
+    kind: ApplicationCommandType,
+    target_id: Option<u64>,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    invoker: UserId,
+    options: Vec<OptionBuilder>,
+    users: Map<String, Value>,
+    members: Map<String, Value>,
+    roles: Map<String, Value>,
+    channels: Map<String, Value>,
+    attachments: Map<String, Value>,
+}
+
+impl InteractionBuilder {
+    /// Start building an interaction for the (slash) command with the given name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind: ApplicationCommandType::ChatInput,
+            target_id: None,
+            guild_id: Some(GuildId(1)),
+            channel_id: ChannelId(1),
+            invoker: UserId(1),
+            options: Vec::new(),
+            users: Map::new(),
+            members: Map::new(),
+            roles: Map::new(),
+            channels: Map::new(),
+            attachments: Map::new(),
+        }
+    }
+
+    /// Set the guild the command was invoked in
+    pub fn guild_id(mut self, guild_id: GuildId) -> Self {
+        self.guild_id = Some(guild_id);
+        self
+    }
+
+    /// Invoke the command from a direct message rather than a guild
+    pub fn direct_message(mut self) -> Self {
+        self.guild_id = None;
+        self
+    }
+
+    /// Set the channel the command was invoked in
+    pub fn channel_id(mut self, channel_id: ChannelId) -> Self {
+        self.channel_id = channel_id;
+        self
+    }
+
+    /// Set the user who invoked the command
+    pub fn invoked_by(mut self, user_id: UserId) -> Self {
+        self.invoker = user_id;
+        self
+    }
+
+    /// Add a top level option to the command
+    pub fn option(mut self, option: OptionBuilder) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Turn this into a user command invoked on the given user. The user should also be added via
+    /// [`InteractionBuilder::resolved_user`].
+    pub fn target_user(mut self, user_id: UserId) -> Self {
+        self.kind = ApplicationCommandType::User;
+        self.target_id = Some(user_id.0);
+        self
+    }
+
+    /// Add a user to the resolved data of the interaction
+    pub fn resolved_user(mut self, user_id: UserId, name: impl Into<String>) -> Self {
+        self.users
+            .insert(user_id.to_string(), user_json(user_id, name.into()));
+        self
+    }
+
+    /// Add a guild member to the resolved data of the interaction
+    pub fn resolved_member(mut self, user_id: UserId, nick: Option<&str>) -> Self {
+        self.members.insert(
+            user_id.to_string(),
+            json!({
+                "nick": nick,
+                "roles": [],
+                "joined_at": "2022-01-01T00:00:00.000Z",
+                "deaf": false,
+                "mute": false,
+                "permissions": "0",
+            }),
+        );
+        self
+    }
+
+    /// Add a role to the resolved data of the interaction
+    pub fn resolved_role(mut self, role_id: RoleId, name: impl Into<String>) -> Self {
+        self.roles.insert(
+            role_id.to_string(),
+            json!({
+                "id": role_id.to_string(),
+                "name": name.into(),
+                "color": 0,
+                "hoist": false,
+                "managed": false,
+                "mentionable": true,
+                "permissions": "0",
+                "position": 1,
+            }),
+        );
+        self
+    }
+
+    /// Add a channel to the resolved data of the interaction
+    pub fn resolved_channel(
+        mut self,
+        channel_id: ChannelId,
+        name: impl Into<String>,
+        kind: ChannelType,
+    ) -> Self {
+        self.channels.insert(
+            channel_id.to_string(),
+            json!({
+                "id": channel_id.to_string(),
+                "name": name.into(),
+                "type": kind.num(),
+                "permissions": "0",
+            }),
+        );
+        self
+    }
+
+    /// Add an attachment to the resolved data of the interaction
+    pub fn resolved_attachment(
+        mut self,
+        attachment_id: AttachmentId,
+        filename: impl Into<String>,
+    ) -> Self {
+        let filename = filename.into();
+        let url = format!(
+            "https://cdn.discordapp.com/attachments/0/{}/{}",
+            attachment_id, filename
+        );
+        self.attachments.insert(
+            attachment_id.to_string(),
+            json!({
+                "id": attachment_id.to_string(),
+                "filename": filename,
+                "size": 0,
+                "url": url,
+                "proxy_url": url,
+            }),
+        );
+        self
+    }
+
+    /// The JSON payload Discord would send for this interaction
+    pub fn to_json(&self, kind: InteractionType) -> Value {
+        let invoker = self
+            .users
+            .get(&self.invoker.to_string())
+            .cloned()
+            .unwrap_or_else(|| user_json(self.invoker, "invoker".to_owned()));
+        let mut roles = self.roles.clone();
+        if let Some(guild_id) = self.guild_id {
+            for role in roles.values_mut() {
+                role["guild_id"] = json!(guild_id.to_string());
+            }
+        }
+        let mut payload = json!({
+            "id": "1",
+            "application_id": "1",
+            "type": kind.num(),
+            "data": {
+                "id": "1",
+                "name": self.name,
+                "type": self.kind.num(),
+                "options": self.options.iter().map(OptionBuilder::to_json).collect::<Vec<_>>(),
+                "resolved": {
+                    "users": self.users,
+                    "members": self.members,
+                    "roles": roles,
+                    "channels": self.channels,
+                    "attachments": self.attachments,
+                },
+            },
+            "channel_id": self.channel_id.to_string(),
+            "token": "token",
+            "version": 1,
+            "locale": "en-US",
+        });
+        if let Some(target_id) = self.target_id {
+            payload["data"]["target_id"] = json!(target_id.to_string());
+        }
+        add_invoker(&mut payload, self.guild_id, invoker);
+        payload
+    }
+
+    /// Build the application command interaction
+    ///
+    /// # Panics
+    /// Panics if serenity is unable to deserialize the interaction (this indicates a bug in the
+    /// builder)
+    pub fn build(&self) -> ApplicationCommandInteraction {
+        serde_json::from_value(self.to_json(InteractionType::ApplicationCommand))
+            .expect("Failed to deserialize application command interaction")
+    }
+
+    /// Build an autocomplete interaction - use [`OptionBuilder::focused`] to mark which option the
+    /// user is filling in
+    ///
+    /// # Panics
+    /// Panics if serenity is unable to deserialize the interaction (this indicates a bug in the
+    /// builder)
+    pub fn build_autocomplete(&self) -> AutocompleteInteraction {
+        serde_json::from_value(self.to_json(InteractionType::Autocomplete))
+            .expect("Failed to deserialize autocomplete interaction")
+    }
+}
+
+/// Builds a [`ModalSubmitInteraction`] offline, so that [`Modal`](crate::Modal)s can be parsed in
+/// tests without a connection to Discord.
+///
+/// Each text input is submitted in its own action row, as Discord does for modals with one input
+/// per row. By default, the modal is submitted from guild `1` in channel `1` by user `1`.
+#[derive(Debug, Clone)]
+pub struct ModalSubmitBuilder {
+    custom_id: String,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    invoker: UserId,
+    inputs: Vec<(String, String)>,
+}
+
+impl ModalSubmitBuilder {
+    /// Start building a submission of the modal with the given custom ID
+    pub fn new(custom_id: impl Into<String>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            guild_id: Some(GuildId(1)),
+            channel_id: ChannelId(1),
+            invoker: UserId(1),
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Submit the modal from a direct message rather than a guild
+    pub fn direct_message(mut self) -> Self {
+        self.guild_id = None;
+        self
+    }
+
+    /// Submit the modal from the given channel
+    pub fn channel_id(mut self, channel_id: ChannelId) -> Self {
+        self.channel_id = channel_id;
+        self
+    }
+
+    /// Submit the modal as the given user
+    pub fn invoked_by(mut self, user_id: UserId) -> Self {
+        self.invoker = user_id;
+        self
+    }
+
+    /// Add the value the user entered into the text input with the given custom ID
+    pub fn input(mut self, custom_id: impl Into<String>, value: impl Into<String>) -> Self {
+        self.inputs.push((custom_id.into(), value.into()));
+        self
+    }
+
+    /// The JSON payload Discord would send for this submission
+    pub fn to_json(&self) -> Value {
+        let rows = self
+            .inputs
+            .iter()
+            .map(|(custom_id, value)| {
+                json!({
+                    "type": ComponentType::ActionRow.num(),
+                    "components": [{
+                        "type": ComponentType::InputText.num(),
+                        "custom_id": custom_id,
+                        "value": value,
+                    }],
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut payload = json!({
+            "id": "1",
+            "application_id": "1",
+            "type": InteractionType::ModalSubmit.num(),
+            "data": {
+                "custom_id": self.custom_id,
+                "components": rows,
+            },
+            "channel_id": self.channel_id.to_string(),
+            "token": "token",
+            "version": 1,
+            "locale": "en-US",
+        });
+        let invoker = user_json(self.invoker, "invoker".to_owned());
+        add_invoker(&mut payload, self.guild_id, invoker);
+        payload
+    }
+
+    /// Build the modal submit interaction
+    ///
+    /// # Panics
+    /// Panics if serenity is unable to deserialize the interaction (this indicates a bug in the
+    /// builder)
+    pub fn build(&self) -> ModalSubmitInteraction {
+        serde_json::from_value(self.to_json())
+            .expect("Failed to deserialize modal submit interaction")
+    }
+}
+
+/// Builds a [`MessageComponentInteraction`] offline, so that components such as
+/// [`Buttons`](crate::Buttons) can be parsed in tests without a connection to Discord.
+///
+/// By default, the interaction is a click on a button, on message `1` sent by the bot in channel
+/// `1` of guild `1`, by user `1`.
+#[derive(Debug, Clone)]
+pub struct ComponentInteractionBuilder {
+    custom_id: String,
+    component_type: ComponentType,
+    values: Vec<String>,
+    message_id: MessageId,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    invoker: UserId,
+}
+
+impl ComponentInteractionBuilder {
+    /// Start building a click on the button with the given custom ID
+    pub fn new(custom_id: impl Into<String>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            component_type: ComponentType::Button,
+            values: Vec::new(),
+            message_id: MessageId(1),
+            guild_id: Some(GuildId(1)),
+            channel_id: ChannelId(1),
+            invoker: UserId(1),
+        }
+    }
+
+    /// Make this a selection in a select menu rather than a button click, with the values of the
+    /// options the user selected
+    pub fn values<I, V>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<String>,
+    {
+        self.component_type = ComponentType::SelectMenu;
+        self.values = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The message the component is attached to
+    pub fn message_id(mut self, message_id: MessageId) -> Self {
+        self.message_id = message_id;
+        self
+    }
+
+    /// Use the component from a direct message rather than a guild
+    pub fn direct_message(mut self) -> Self {
+        self.guild_id = None;
+        self
+    }
+
+    /// Use the component in the given channel
+    pub fn channel_id(mut self, channel_id: ChannelId) -> Self {
+        self.channel_id = channel_id;
+        self
+    }
+
+    /// Use the component as the given user
+    pub fn invoked_by(mut self, user_id: UserId) -> Self {
+        self.invoker = user_id;
+        self
+    }
+
+    /// The JSON payload Discord would send for this interaction
+    pub fn to_json(&self) -> Value {
+        let mut payload = json!({
+            "id": "1",
+            "application_id": "1",
+            "type": InteractionType::MessageComponent.num(),
+            "data": {
+                "custom_id": self.custom_id,
+                "component_type": self.component_type.num(),
+                "values": self.values,
+            },
+            "message": {
+                "id": self.message_id.to_string(),
+                "channel_id": self.channel_id.to_string(),
+                "author": user_json(UserId(1), "bot".to_owned()),
+                "content": "",
+                "timestamp": "2022-01-01T00:00:00.000Z",
+                "edited_timestamp": null,
+                "tts": false,
+                "mention_everyone": false,
+                "mentions": [],
+                "mention_roles": [],
+                "attachments": [],
+                "embeds": [],
+                "components": [],
+                "pinned": false,
+                "type": 0,
+            },
+            "channel_id": self.channel_id.to_string(),
+            "token": "token",
+            "version": 1,
+            "locale": "en-US",
+        });
+        if let Some(guild_id) = self.guild_id {
+            payload["message"]["guild_id"] = json!(guild_id.to_string());
+        }
+        let invoker = user_json(self.invoker, "invoker".to_owned());
+        add_invoker(&mut payload, self.guild_id, invoker);
+        payload
+    }
+
+    /// Build the message component interaction
+    ///
+    /// # Panics
+    /// Panics if serenity is unable to deserialize the interaction (this indicates a bug in the
+    /// builder)
+    pub fn build(&self) -> MessageComponentInteraction {
+        serde_json::from_value(self.to_json())
+            .expect("Failed to deserialize message component interaction")
+    }
+}
+
+/// Add the user who triggered the interaction - as a member when it was in a guild, or just as a
+/// user in direct messages
+fn add_invoker(payload: &mut Value, guild_id: Option<GuildId>, invoker: Value) {
+    match guild_id {
+        Some(guild_id) => {
+            payload["guild_id"] = json!(guild_id.to_string());
+            payload["member"] = json!({
+                "user": invoker,
+                "roles": [],
+                "joined_at": "2022-01-01T00:00:00.000Z",
+                "deaf": false,
+                "mute": false,
+                "permissions": "0",
+            });
+        }
+        None => payload["user"] = invoker,
+    }
+}
+
+fn user_json(user_id: UserId, name: String) -> Value {
+    json!({
+        "id": user_id.to_string(),
+        "username": name,
+        "discriminator": "0001",
+        "avatar": null,
+    })
+}
+
+/// Builds a single option (or subcommand / subcommand group) for an [`InteractionBuilder`]
+///
+/// Options referring to users, roles, channels or attachments only hold the id - the referenced
+/// object must be added to the resolved data via the [`InteractionBuilder`] for the option to parse.
+#[derive(Debug, Clone)]
+pub struct OptionBuilder {
+    name: String,
+    kind: ApplicationCommandOptionType,
+    value: Option<Value>,
+    focused: bool,
+    options: Vec<OptionBuilder>,
+}
+
+impl OptionBuilder {
+    fn new(
+        name: impl Into<String>,
+        kind: ApplicationCommandOptionType,
+        value: Option<Value>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            value,
+            focused: false,
+            options: Vec::new(),
+        }
+    }
+
+    /// A subcommand - add its options via [`OptionBuilder::option`]
+    pub fn sub_command(name: impl Into<String>) -> Self {
+        Self::new(name, ApplicationCommandOptionType::SubCommand, None)
+    }
+
+    /// A subcommand group - add its subcommand via [`OptionBuilder::option`]
+    pub fn sub_command_group(name: impl Into<String>) -> Self {
+        Self::new(name, ApplicationCommandOptionType::SubCommandGroup, None)
+    }
+
+    /// A STRING option
+    pub fn string(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::String,
+            Some(json!(value.into())),
+        )
+    }
+
+    /// An INTEGER option
+    pub fn integer(name: impl Into<String>, value: i64) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::Integer,
+            Some(json!(value)),
+        )
+    }
+
+    /// A BOOLEAN option
+    pub fn boolean(name: impl Into<String>, value: bool) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::Boolean,
+            Some(json!(value)),
+        )
+    }
+
+    /// A NUMBER option
+    pub fn number(name: impl Into<String>, value: f64) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::Number,
+            Some(json!(value)),
+        )
+    }
+
+    /// A USER option
+    pub fn user(name: impl Into<String>, user_id: UserId) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::User,
+            Some(json!(user_id.to_string())),
+        )
+    }
+
+    /// A CHANNEL option
+    pub fn channel(name: impl Into<String>, channel_id: ChannelId) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::Channel,
+            Some(json!(channel_id.to_string())),
+        )
+    }
+
+    /// A ROLE option
+    pub fn role(name: impl Into<String>, role_id: RoleId) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::Role,
+            Some(json!(role_id.to_string())),
+        )
+    }
+
+    /// A MENTIONABLE option, referring to either a user or a role
+    pub fn mentionable(name: impl Into<String>, id: u64) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::Mentionable,
+            Some(json!(id.to_string())),
+        )
+    }
+
+    /// An ATTACHMENT option
+    pub fn attachment(name: impl Into<String>, attachment_id: AttachmentId) -> Self {
+        Self::new(
+            name,
+            ApplicationCommandOptionType::Attachment,
+            Some(json!(attachment_id.to_string())),
+        )
+    }
+
+    /// Mark this option as the one the user is currently filling in (for autocomplete interactions)
+    pub fn focused(mut self) -> Self {
+        self.focused = true;
+        self
+    }
+
+    /// Add a nested option to this subcommand or subcommand group
+    pub fn option(mut self, option: OptionBuilder) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut option = json!({
+            "name": self.name,
+            "type": self.kind.num(),
+        });
+        if let Some(ref value) = self.value {
+            option["value"] = value.clone();
+        }
+        if self.focused {
+            option["focused"] = json!(true);
+        }
+        if !self.options.is_empty() {
+            option["options"] = self.options.iter().map(OptionBuilder::to_json).collect();
+        }
+        option
+    }
+}
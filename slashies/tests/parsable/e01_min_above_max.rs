@@ -0,0 +1,5 @@
+use slashies::parsable::BoundedInt;
+
+fn main() {
+    BoundedInt::<3, 1>::new(2);
+}
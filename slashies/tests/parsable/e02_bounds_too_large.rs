@@ -0,0 +1,7 @@
+use serenity::builder::CreateApplicationCommandOption;
+use slashies::parsable::{BoundedNumber, ParsableCommandOption};
+
+fn main() {
+    let mut option = CreateApplicationCommandOption::default();
+    BoundedNumber::<0, { i64::MAX }>::register_option(&mut option);
+}
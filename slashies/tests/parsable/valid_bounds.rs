@@ -0,0 +1,8 @@
+use serenity::builder::CreateApplicationCommandOption;
+use slashies::parsable::{BoundedInt, BoundedNumber, ParsableCommandOption};
+
+fn main() {
+    let mut option = CreateApplicationCommandOption::default();
+    BoundedInt::<{ -(1 << 53) }, { 1 << 53 }>::register_option(&mut option);
+    BoundedNumber::<1, 1>::register_option(&mut option);
+}